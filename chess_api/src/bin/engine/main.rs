@@ -0,0 +1,24 @@
+use std::io::{self, BufRead};
+use std::iter;
+
+mod uci;
+mod xboard;
+
+/// protocol is chosen by the first command, `xboard` for CECP, UCI otherwise
+fn main() {
+    let mut first = String::new();
+
+    match io::stdin().read_line(&mut first) {
+        Ok(0) | Err(_) => return,
+        Ok(_) => ()
+    }
+
+    if first.trim() == "xboard" {
+        xboard::run();
+    } else {
+        let stdin = io::stdin();
+        let lines = stdin.lock().lines().map_while(Result::ok);
+
+        uci::run(iter::once(first).chain(lines));
+    }
+}
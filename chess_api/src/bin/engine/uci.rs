@@ -0,0 +1,180 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use chess_api::board::Board;
+use chess_api::engine::{Engine, Strength};
+use chess_api::game::Game;
+use chess_api::uci::{Command, GoOptions, Info};
+
+const NAME: &str = "term_chess engine";
+const AUTHOR: &str = "term_chess developers";
+
+const DEFAULT_HASH_SIZE: usize = 16;
+const MAX_HASH_SIZE: usize = 1024;
+
+/// # Engine state between commands
+struct Session {
+    /// engine is moved into search thread while searching
+    engine: Option<Engine>,
+    search: Option<JoinHandle<Engine>>,
+    stop: Arc<AtomicBool>,
+    /// dropped to let infinite search send its best move
+    release: Option<Sender<()>>,
+    game: Game,
+    /// castling is written as king capturing own rook
+    chess960: bool,
+    debug: bool
+}
+
+/// # Answers UCI commands until `quit` or end of input
+pub fn run(lines: impl Iterator<Item = String>) {
+    let engine = Engine::with_hash_size(DEFAULT_HASH_SIZE);
+
+    let mut session = Session {
+        stop: engine.stop_flag(),
+        engine: Some(engine),
+        search: None,
+        release: None,
+        game: Game::new(),
+        chess960: false,
+        debug: false
+    };
+
+    for line in lines {
+        match line.parse() {
+            Ok(Command::Quit) => break,
+            Ok(command) => session.handle(command),
+            Err(e) => session.debug(&format!("{}: {}", e, line))
+        }
+    }
+
+    session.stop_search();
+}
+
+impl Session {
+    fn handle(&mut self, command: Command) {
+        match command {
+            Command::Uci => {
+                println!("id name {}", NAME);
+                println!("id author {}", AUTHOR);
+                println!("option name Hash type spin default {} min 1 max {}", DEFAULT_HASH_SIZE, MAX_HASH_SIZE);
+                println!("option name Clear Hash type button");
+                println!("option name Skill Level type spin default {} min {} max {}", Strength::MAX_LEVEL, Strength::MIN_LEVEL, Strength::MAX_LEVEL);
+                println!("option name UCI_Chess960 type check default false");
+                println!("uciok");
+            },
+            Command::Debug(on) => self.debug = on,
+            Command::IsReady => println!("readyok"),
+            Command::SetOption { name, value } => self.set_option(&name, value.as_deref()),
+            Command::UciNewGame => {
+                self.stop_search();
+                self.engine().clear();
+                self.game = Game::new();
+            },
+            Command::Position { fen, moves } => {
+                self.stop_search();
+
+                let mut board = match fen {
+                    Some(fen) => match Board::from_fen(&fen) {
+                        Ok(board) => board,
+                        Err(e) => return self.debug(&format!("invalid fen: {}", e))
+                    },
+                    None => Board::new()
+                };
+
+                if self.chess960 {
+                    board.set_chess960(true);
+                }
+
+                self.game = Game::from_board(board);
+
+                for m in moves {
+                    if let Err(reason) = self.game.play(m) {
+                        return self.debug(&format!("illegal move {}: {:?}", m, reason));
+                    }
+                }
+            },
+            Command::Go(go) => self.go(go),
+            Command::Stop => self.stop_search(),
+            // pondering is not advertised, so ponder hit just ends the search with move found while pondering
+            Command::PonderHit => self.stop_search(),
+            Command::Quit => self.stop_search()
+        }
+    }
+
+    fn set_option(&mut self, name: &str, value: Option<&str>) {
+        self.stop_search();
+
+        match (name.to_lowercase().as_str(), value.map(str::parse::<usize>)) {
+            ("hash", Some(Ok(megabytes))) => {
+                let strength = self.engine().strength();
+
+                let mut engine = Engine::with_hash_size(megabytes.clamp(1, MAX_HASH_SIZE));
+                engine.set_strength(strength);
+
+                self.stop = engine.stop_flag();
+                self.engine = Some(engine);
+            },
+            ("clear hash", _) => self.engine().clear(),
+            ("skill level", Some(Ok(level))) => self.engine().set_strength(Strength::level(level as u32)),
+            ("uci_chess960", _) => self.chess960 = value == Some("true"),
+            _ => self.debug(&format!("unsupported option {} = {:?}", name, value))
+        }
+    }
+
+    fn go(&mut self, go: GoOptions) {
+        self.stop_search();
+
+        let mut engine = self.engine.take().unwrap();
+        let game = self.game.clone();
+        let limits = go.limits(game.board().side_to_move());
+        let (release, released) = mpsc::channel::<()>();
+
+        self.stop.store(false, Ordering::Relaxed);
+        self.release = Some(release);
+
+        self.search = Some(thread::spawn(move || {
+            let best = engine.search_game_with_info(&game, limits, |best| println!("{}", Info::from(best)));
+
+            // best move of infinite search must not be sent before stop, receiving ends when sender is dropped
+            if go.infinite || go.ponder {
+                let _ = released.recv();
+            }
+
+            match best.get_move() {
+                Some(m) => println!("bestmove {}", m),
+                None => println!("bestmove 0000")
+            }
+
+            engine
+        }));
+    }
+
+    /// # Ends running search, best move found so far is sent
+    ///
+    /// commands changing engine's state are not expected during search, they stop it first
+    ///
+    fn stop_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            self.stop.store(true, Ordering::Relaxed);
+            self.release = None;
+            self.engine = Some(search.join().expect("search thread panicked"));
+        }
+    }
+
+    fn engine(&mut self) -> &mut Engine {
+        self.stop_search();
+        self.engine.as_mut().unwrap()
+    }
+
+    /// # Sends message to GUI when debug mode is on, errors are always written to stderr
+    fn debug(&self, message: &str) {
+        if self.debug {
+            println!("{}", Info { string: Some(message.to_string()), ..Info::default() });
+        }
+
+        eprintln!("{}", message);
+    }
+}
@@ -0,0 +1,280 @@
+use std::io::{self, BufRead};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use chess_api::board::{Board, PostMoveState};
+use chess_api::cecp::{parse_move, Command, Thinking};
+use chess_api::engine::{BestMove, Engine, SearchLimits};
+use chess_api::game::Game;
+use chess_api::piece::PieceColor;
+use chess_api::uci::CommandError;
+
+const NAME: &str = "term_chess engine";
+
+/// time per move when interface sends neither clock nor fixed time
+const DEFAULT_MOVE_TIME: Duration = Duration::from_secs(5);
+
+/// commands which are accepted, but have no effect
+const IGNORED: [&str; 8] = ["random", "computer", "name", "rating", "ics", "bk", "hint", "easy"];
+
+enum Event {
+    Line(String),
+    /// end of input
+    Closed,
+    /// search with given number has finished, its result is taken by joining it
+    SearchDone(u32)
+}
+
+/// # Engine state between commands
+struct Session {
+    /// engine is moved into search thread while searching
+    engine: Option<Engine>,
+    search: Option<JoinHandle<(Engine, BestMove)>>,
+    /// number of the last started search, results of cancelled searches are ignored
+    search_id: u32,
+    stop: Arc<AtomicBool>,
+    events: Sender<Event>,
+    game: Game,
+    /// engine does not move in force mode
+    force: bool,
+    engine_color: PieceColor,
+    post: bool,
+    time: Option<Duration>,
+    level: (u32, Duration),
+    move_time: Option<Duration>,
+    depth: Option<u32>
+}
+
+/// # Answers CECP commands until `quit` or end of input, `xboard` was already received
+pub fn run() {
+    let (events, receiver) = mpsc::channel();
+    let lines = events.clone();
+
+    // lines are read in separate thread, so that commands can be handled during search
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(Result::ok) {
+            if lines.send(Event::Line(line)).is_err() {
+                return;
+            }
+        }
+
+        let _ = lines.send(Event::Closed);
+    });
+
+    let engine = Engine::new();
+
+    let mut session = Session {
+        stop: engine.stop_flag(),
+        engine: Some(engine),
+        search: None,
+        search_id: 0,
+        events,
+        game: Game::new(),
+        force: false,
+        engine_color: PieceColor::BLACK,
+        post: false,
+        time: None,
+        level: (0, Duration::ZERO),
+        move_time: None,
+        depth: None
+    };
+
+    for event in receiver {
+        match event {
+            Event::Line(line) => match line.parse() {
+                Ok(Command::Quit) => break,
+                Ok(command) => session.handle(command),
+                Err(CommandError::Empty) => (),
+                Err(CommandError::UnknownCommand(command)) if IGNORED.contains(&command.as_str()) => (),
+                // protocol version 1 sends moves without usermove
+                Err(CommandError::UnknownCommand(_)) if parse_move(session.game.board(), line.trim()).is_ok() => {
+                    session.user_move(line.trim());
+                },
+                Err(CommandError::UnknownCommand(command)) => println!("Error (unknown command): {}", command),
+                Err(e) => println!("Error ({}): {}", e, line)
+            },
+            Event::Closed => break,
+            Event::SearchDone(id) if id == session.search_id => session.finish_search(),
+            Event::SearchDone(_) => ()
+        }
+    }
+
+    session.cancel_search();
+}
+
+impl Session {
+    fn handle(&mut self, command: Command) {
+        match command {
+            Command::Protover(_) => {
+                println!("feature myname=\"{}\" usermove=1 setboard=1 ping=1 playother=1 san=0 colors=0 sigint=0 sigterm=0 analyze=0 done=1", NAME);
+            },
+            Command::New => {
+                self.cancel_search();
+                self.engine().clear();
+                self.game = Game::new();
+                self.force = false;
+                self.engine_color = PieceColor::BLACK;
+                self.move_time = None;
+                self.depth = None;
+            },
+            Command::Variant(variant) => println!("Error (unsupported variant): {}", variant),
+            Command::Force | Command::Result { .. } => {
+                self.cancel_search();
+                self.force = true;
+            },
+            Command::Go => {
+                self.cancel_search();
+                self.force = false;
+                self.engine_color = self.game.board().side_to_move();
+                self.start_search();
+            },
+            Command::PlayOther => {
+                self.cancel_search();
+                self.force = false;
+                self.engine_color = !self.game.board().side_to_move();
+            },
+            Command::UserMove(m) => self.user_move(&m),
+            Command::MoveNow => self.stop.store(true, Ordering::Relaxed),
+            Command::Time(time) => self.time = Some(time),
+            Command::Level { moves, increment, .. } => self.level = (moves, increment),
+            Command::St(time) => self.move_time = Some(time),
+            Command::Sd(depth) => self.depth = Some(depth),
+            Command::SetBoard(fen) => {
+                self.cancel_search();
+
+                match Board::from_fen(&fen) {
+                    Ok(board) => self.game = Game::from_board(board),
+                    Err(e) => println!("tellusererror Illegal position: {}", e)
+                }
+            },
+            Command::Undo | Command::Remove => {
+                self.cancel_search();
+
+                let count = if command == Command::Undo { 1 } else { 2 };
+
+                for _ in 0..count {
+                    self.game.undo_move();
+                }
+            },
+            Command::Post => self.post = true,
+            Command::NoPost => self.post = false,
+            // commands are handled in order, so all before ping are done, running search is not waited for
+            Command::Ping(n) => println!("pong {}", n),
+            // draw offers are declined
+            Command::Draw => (),
+            Command::Xboard | Command::Accepted(_) | Command::Rejected(_) | Command::Hard | Command::Easy | Command::Otim(_) | Command::Quit => ()
+        }
+    }
+
+    fn user_move(&mut self, s: &str) {
+        self.cancel_search();
+
+        let m = match parse_move(self.game.board(), s) {
+            Ok(m) => m,
+            Err(_) => return println!("Illegal move: {}", s)
+        };
+
+        self.game.play(m).unwrap();
+
+        if !self.report_result() && !self.force && self.game.board().side_to_move() == self.engine_color {
+            self.start_search();
+        }
+    }
+
+    fn limits(&self) -> SearchLimits {
+        let mut limits = match (self.move_time, self.time) {
+            (Some(time), _) => SearchLimits::time(time),
+            (None, Some(remaining)) => {
+                let (moves_per_session, increment) = self.level;
+
+                // moves left until the next time control
+                let moves_to_go = match moves_per_session {
+                    0 => None,
+                    moves => Some(moves - (self.game.board().fullmove_number() - 1) % moves)
+                };
+
+                SearchLimits::from_clock(remaining, increment, moves_to_go)
+            },
+            (None, None) => SearchLimits::time(DEFAULT_MOVE_TIME)
+        };
+
+        limits.depth = self.depth;
+        limits
+    }
+
+    fn start_search(&mut self) {
+        let mut engine = self.engine.take().unwrap();
+        let game = self.game.clone();
+        let limits = self.limits();
+        let post = self.post;
+        let events = self.events.clone();
+
+        self.search_id += 1;
+        self.stop.store(false, Ordering::Relaxed);
+
+        let id = self.search_id;
+
+        self.search = Some(thread::spawn(move || {
+            let best = engine.search_game_with_info(&game, limits, |best| if post {
+                println!("{}", Thinking::new(best, game.board()));
+            });
+
+            // main loop may be gone already when engine quits
+            let _ = events.send(Event::SearchDone(id));
+            (engine, best)
+        }));
+    }
+
+    /// # Plays move found by finished search
+    fn finish_search(&mut self) {
+        let (engine, best) = match self.search.take() {
+            Some(search) => search.join().expect("search thread panicked"),
+            None => return
+        };
+
+        self.engine = Some(engine);
+
+        if let Some(m) = best.get_move() {
+            self.game.play(m).unwrap();
+            println!("move {}", m);
+            self.report_result();
+        }
+    }
+
+    /// # Ends running search without playing its move
+    fn cancel_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            self.stop.store(true, Ordering::Relaxed);
+            self.engine = Some(search.join().expect("search thread panicked").0);
+        }
+    }
+
+    /// # Sends result when the game has ended, returns true if it has
+    fn report_result(&self) -> bool {
+        let winner = match self.game.board().side_to_move() {
+            PieceColor::WHITE => ("0-1", "Black mates"),
+            PieceColor::BLACK => ("1-0", "White mates")
+        };
+
+        let (result, comment) = match self.game.last_move().map(|m| m.state()) {
+            Some(PostMoveState::Checkmate) => winner,
+            Some(PostMoveState::Stelemate) => ("1/2-1/2", "Stalemate"),
+            Some(PostMoveState::Draw(reason)) => {
+                println!("1/2-1/2 {{{:?}}}", reason);
+                return true;
+            },
+            _ => return false
+        };
+
+        println!("{} {{{}}}", result, comment);
+        true
+    }
+
+    fn engine(&mut self) -> &mut Engine {
+        self.cancel_search();
+        self.engine.as_mut().unwrap()
+    }
+}
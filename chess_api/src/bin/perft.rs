@@ -0,0 +1,52 @@
+use std::env;
+use std::process;
+use std::time::Instant;
+
+use chess_api::board::Board;
+
+const USAGE: &str = "usage: perft [divide] <depth> [fen]";
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    let divide = args.first().is_some_and(|arg| arg == "divide");
+    if divide {
+        args.remove(0);
+    }
+
+    let depth = match args.first().map(|arg| arg.parse::<u32>()) {
+        Some(Ok(depth)) => depth,
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    let board = if args.len() > 1 {
+        match Board::from_fen(&args[1..].join(" ")) {
+            Ok(board) => board,
+            Err(e) => {
+                eprintln!("invalid fen: {}", e);
+                process::exit(2);
+            }
+        }
+    } else { Board::new() };
+
+    let start = Instant::now();
+
+    let nodes = if divide {
+        let divided = board.divide(depth);
+
+        for (m, nodes) in divided.iter() {
+            println!("{}: {}", m, nodes);
+        }
+
+        println!();
+        divided.iter().map(|(_, nodes)| nodes).sum()
+    } else { board.perft(depth) };
+
+    let elapsed = start.elapsed();
+
+    println!("nodes: {}", nodes);
+    println!("time: {:.3}s ({:.0} nps)", elapsed.as_secs_f64(), nodes as f64 / elapsed.as_secs_f64().max(1e-9));
+}
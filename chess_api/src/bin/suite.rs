@@ -0,0 +1,69 @@
+use std::env;
+use std::fs;
+use std::process;
+use std::time::Duration;
+
+use chess_api::engine::Engine;
+use chess_api::epd::{read_epd, run_suite};
+
+/// searches every position of EPD test suite, e.g. `suite wac.epd 1 5`, with given times in seconds
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let path = match args.first() {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: suite <file.epd> [seconds per position]...");
+            process::exit(2);
+        }
+    };
+
+    let times: Option<Vec<Duration>> = args[1..].iter()
+        .map(|s| s.parse::<f64>().ok().filter(|seconds| *seconds > 0.0).map(Duration::from_secs_f64))
+        .collect();
+
+    let times = match times {
+        Some(times) if !times.is_empty() => times,
+        Some(_) => vec![Duration::from_secs(1)],
+        None => {
+            eprintln!("times have to be positive numbers of seconds");
+            process::exit(2);
+        }
+    };
+
+    let positions = match fs::read_to_string(path).map(|text| read_epd(&text)) {
+        Ok(Ok(positions)) => positions,
+        Ok(Err((line, e))) => {
+            eprintln!("{}:{}: {}", path, line, e);
+            process::exit(1);
+        },
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            process::exit(1);
+        }
+    };
+
+    let mut engine = Engine::new();
+    let mut reports = Vec::new();
+
+    for time in times {
+        println!("{} positions, {:?} each", positions.len(), time);
+
+        reports.push(run_suite(&mut engine, &positions, time, |result| {
+            let found = result.found.map(|m| m.to_string()).unwrap_or_else(|| "none".to_string());
+            println!("  {:<20} {:<6} {}", result.id, found, if result.solved { "solved" } else { "failed" });
+        }));
+    }
+
+    println!();
+
+    for report in reports {
+        let failed: Vec<&str> = report.failed().map(|result| result.id.as_str()).collect();
+
+        println!("{:?}: {}/{} solved", report.time, report.solved(), report.results.len());
+
+        if !failed.is_empty() {
+            println!("  failed: {}", failed.join(", "));
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use crate::movement::Square;
+use crate::piece::PieceColor;
+
+
+/// # Set of squares stored as 64 bit number
+///
+/// bit `i` is set when square with index `i` belongs to the set
+///
+/// ```
+/// use chess_api::bitboard::Bitboard;
+/// use chess_api::movement::Square;
+///
+/// let set = Bitboard::from_square(Square::new(0, 0)) | Bitboard::from_square(Square::new(7, 7));
+///
+/// assert_eq!(set.count(), 2);
+/// assert!(set.contains(Square::new(7, 7)));
+/// assert_eq!(set.squares().collect::<Vec<_>>(), [Square::new(0, 0), Square::new(7, 7)]);
+/// ```
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard(0);
+    pub const FULL: Bitboard = Bitboard(!0);
+
+    pub fn from_square(square: Square) -> Bitboard {
+        Bitboard(1 << square.to_index())
+    }
+
+    pub fn contains(&self, square: Square) -> bool {
+        self.0 & (1 << square.to_index()) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// # Returns square with the lowest index
+    pub fn first(&self) -> Option<Square> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(Square::from_index(self.0.trailing_zeros() as usize))
+        }
+    }
+
+    /// # Returns iterator over squares in the set, from the lowest index
+    pub fn squares(self) -> impl Iterator<Item = Square> {
+        let mut bits = self.0;
+
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                None
+            } else {
+                let index = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(Square::from_index(index))
+            }
+        })
+    }
+}
+
+impl BitAnd for Bitboard {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Bitboard(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Bitboard(self.0 | rhs.0)
+    }
+}
+
+impl BitXor for Bitboard {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Bitboard(self.0 ^ rhs.0)
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Bitboard(!self.0)
+    }
+}
+
+impl BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitXorAssign for Bitboard {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.0 ^= rhs.0;
+    }
+}
+
+// precomputed tables
+/// # Returns set of squares reachable by given steps from given index
+const fn step_attacks(index: usize, steps: &[(i8, i8)]) -> u64 {
+    let x = (index % 8) as i8;
+    let y = (index / 8) as i8;
+    let mut attacks = 0;
+
+    let mut i = 0;
+    while i < steps.len() {
+        let (tx, ty) = (x + steps[i].0, y + steps[i].1);
+
+        if tx >= 0 && tx < 8 && ty >= 0 && ty < 8 {
+            attacks |= 1 << (tx + 8 * ty);
+        }
+
+        i += 1;
+    }
+
+    attacks
+}
+
+const fn step_table(steps: &[(i8, i8)]) -> [u64; 64] {
+    let mut table = [0; 64];
+
+    let mut i = 0;
+    while i < 64 {
+        table[i] = step_attacks(i, steps);
+        i += 1;
+    }
+
+    table
+}
+
+/// # Returns set of squares on line going through given index in given direction, without the index itself
+const fn line_mask(index: usize, dx: i8, dy: i8) -> u64 {
+    let mut mask = 0;
+
+    let mut sign = -1;
+    while sign <= 1 {
+        let (mut x, mut y) = ((index % 8) as i8 + sign * dx, (index / 8) as i8 + sign * dy);
+
+        while x >= 0 && x < 8 && y >= 0 && y < 8 {
+            mask |= 1 << (x + 8 * y);
+            x += sign * dx;
+            y += sign * dy;
+        }
+
+        sign += 2;
+    }
+
+    mask
+}
+
+const fn line_table(dx: i8, dy: i8) -> [u64; 64] {
+    let mut table = [0; 64];
+
+    let mut i = 0;
+    while i < 64 {
+        table[i] = line_mask(i, dx, dy);
+        i += 1;
+    }
+
+    table
+}
+
+/// # Attacks of rook on the first rank for every file and occupancy of six inner squares
+const fn first_rank_table() -> [[u8; 64]; 8] {
+    let mut table = [[0; 64]; 8];
+
+    let mut file = 0;
+    while file < 8 {
+        let mut inner = 0;
+        while inner < 64 {
+            let occupied = (inner << 1) as u8;
+            let mut attacks = 0u8;
+
+            let mut x = file + 1;
+            while x < 8 {
+                attacks |= 1 << x;
+                if occupied & (1 << x) != 0 {
+                    break;
+                }
+                x += 1;
+            }
+
+            let mut x = file as i8 - 1;
+            while x >= 0 {
+                attacks |= 1 << x;
+                if occupied & (1 << x) != 0 {
+                    break;
+                }
+                x -= 1;
+            }
+
+            table[file][inner] = attacks;
+            inner += 1;
+        }
+        file += 1;
+    }
+
+    table
+}
+
+const KNIGHT_STEPS: [(i8, i8); 8] = [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)];
+const KING_STEPS: [(i8, i8); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+
+static KNIGHT_ATTACKS: [u64; 64] = step_table(&KNIGHT_STEPS);
+static KING_ATTACKS: [u64; 64] = step_table(&KING_STEPS);
+static PAWN_ATTACKS: [[u64; 64]; 2] = [step_table(&[(-1, 1), (1, 1)]), step_table(&[(-1, -1), (1, -1)])];
+
+static FILE_MASKS: [u64; 64] = line_table(0, 1);
+static DIAGONAL_MASKS: [u64; 64] = line_table(1, 1);
+static ANTI_DIAGONAL_MASKS: [u64; 64] = line_table(1, -1);
+static FIRST_RANK_ATTACKS: [[u8; 64]; 8] = first_rank_table();
+
+/// # Hyperbola quintessence for lines which are not ranks
+fn line_attacks(index: usize, occupied: u64, mask: u64) -> u64 {
+    let slider = 1u64 << index;
+    let forward = occupied & mask;
+    let reverse = forward.swap_bytes();
+
+    let forward = forward.wrapping_sub(slider.wrapping_mul(2));
+    let reverse = reverse.wrapping_sub(slider.swap_bytes().wrapping_mul(2));
+
+    (forward ^ reverse.swap_bytes()) & mask
+}
+
+fn rank_attacks(index: usize, occupied: u64) -> u64 {
+    let shift = index & 56;
+    let inner = (occupied >> (shift + 1)) & 63;
+
+    (FIRST_RANK_ATTACKS[index & 7][inner as usize] as u64) << shift
+}
+
+// attack queries
+pub fn knight_attacks(square: Square) -> Bitboard {
+    Bitboard(KNIGHT_ATTACKS[square.to_index()])
+}
+
+pub fn king_attacks(square: Square) -> Bitboard {
+    Bitboard(KING_ATTACKS[square.to_index()])
+}
+
+/// # Returns squares attacked by pawn of given color standing on given square
+pub fn pawn_attacks(color: PieceColor, square: Square) -> Bitboard {
+    Bitboard(PAWN_ATTACKS[color as usize][square.to_index()])
+}
+
+/// # Returns squares attacked by bishop, attacks include first occupied square in every direction
+///
+/// ```
+/// use chess_api::bitboard::{bishop_attacks, Bitboard};
+/// use chess_api::movement::Square;
+///
+/// let blocker = Bitboard::from_square(Square::new(5, 5));
+/// let attacks = bishop_attacks(Square::new(3, 3), blocker);
+///
+/// assert!(attacks.contains(Square::new(5, 5)));
+/// assert!(!attacks.contains(Square::new(6, 6)));
+/// assert_eq!(attacks.count(), 11);
+/// ```
+pub fn bishop_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    let index = square.to_index();
+    Bitboard(line_attacks(index, occupied.0, DIAGONAL_MASKS[index]) | line_attacks(index, occupied.0, ANTI_DIAGONAL_MASKS[index]))
+}
+
+/// # Returns squares attacked by rook, attacks include first occupied square in every direction
+pub fn rook_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    let index = square.to_index();
+    Bitboard(line_attacks(index, occupied.0, FILE_MASKS[index]) | rank_attacks(index, occupied.0))
+}
+
+pub fn queen_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    bishop_attacks(square, occupied) | rook_attacks(square, occupied)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn step_attacks() {
+        assert_eq!(knight_attacks(Square::new(0, 0)).count(), 2);
+        assert_eq!(knight_attacks(Square::new(3, 3)).count(), 8);
+        assert_eq!(king_attacks(Square::new(7, 7)).count(), 3);
+        assert_eq!(king_attacks(Square::new(4, 4)).count(), 8);
+
+        assert_eq!(pawn_attacks(PieceColor::WHITE, Square::new(0, 1)), Bitboard::from_square(Square::new(1, 2)));
+        assert_eq!(pawn_attacks(PieceColor::BLACK, Square::new(4, 6)), Bitboard::from_square(Square::new(3, 5)) | Bitboard::from_square(Square::new(5, 5)));
+        assert!(pawn_attacks(PieceColor::WHITE, Square::new(4, 7)).is_empty());
+    }
+
+    #[test]
+    fn slider_attacks() {
+        assert_eq!(rook_attacks(Square::new(0, 0), Bitboard::EMPTY).count(), 14);
+        assert_eq!(bishop_attacks(Square::new(0, 0), Bitboard::EMPTY).count(), 7);
+        assert_eq!(queen_attacks(Square::new(3, 3), Bitboard::EMPTY).count(), 27);
+
+        let occupied = Bitboard::from_square(Square::new(3, 5)) | Bitboard::from_square(Square::new(1, 3)) | Bitboard::from_square(Square::new(3, 0));
+        let attacks = rook_attacks(Square::new(3, 3), occupied);
+
+        assert_eq!(attacks.count(), 11);
+        assert!(attacks.contains(Square::new(3, 5)));
+        assert!(!attacks.contains(Square::new(3, 6)));
+        assert!(attacks.contains(Square::new(1, 3)));
+        assert!(!attacks.contains(Square::new(0, 3)));
+        assert!(attacks.contains(Square::new(7, 3)));
+        assert!(attacks.contains(Square::new(3, 0)));
+    }
+}
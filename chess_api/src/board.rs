@@ -1,19 +1,108 @@
 use super::piece::*;
 use super::movement::*;
+use super::bitboard::Bitboard;
 
+mod attacks;
+mod fen;
+mod movegen;
+mod perft;
+mod san;
+mod see;
+mod zobrist;
 
-#[derive(Debug, PartialEq)]
+pub use attacks::Pin;
+pub use fen::FenError;
+pub use movegen::MoveFilter;
+pub use san::SanError;
+
+
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum MoveFailReason {
-    NoSourcePiece, FriendlyFire, IllegalMove, KingAttacked
+    NoSourcePiece, FriendlyFire, IllegalMove, KingAttacked, PromotionRequired, WrongTurn
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum PostMoveState {
-    Normal, Check, Checkmate, Stelemate
+    Normal, Check, Checkmate, Stelemate, Draw(DrawReason)
+}
+
+/// # Rule by which game is drawn
+///
+/// automatic draws end the game immediately,
+/// other draws have to be claimed by a player
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum DrawReason {
+    InsufficientMaterial, SeventyFiveMoveRule, FivefoldRepetition, FiftyMoveRule, ThreefoldRepetition
 }
 
+impl DrawReason {
+    /// # Returns true if draw does not have to be claimed
+    pub fn is_automatic(&self) -> bool {
+        matches!(self, DrawReason::InsufficientMaterial | DrawReason::SeventyFiveMoveRule | DrawReason::FivefoldRepetition)
+    }
+}
+
+/// # Record of performed move needed to take it back
+///
+/// created by `Board::perform_move_with_undo`
+#[derive(Debug, Clone)]
+pub struct MoveUndo {
+    m: Move,
+    piece: Piece,
+    captured: Option<(Square, Piece)>,
+    castling: Option<Castling>,
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    hash: u64
+}
+
+impl MoveUndo {
+    /// # Returns move this record takes back
+    pub fn performed_move(&self) -> Move {
+        self.m
+    }
+
+    /// # Returns piece captured by the move
+    pub fn captured(&self) -> Option<&Piece> {
+        self.captured.as_ref().map(|(_, piece)| piece)
+    }
+}
+
+fn min_max_files(a: Square, b: Square) -> (u8, u8) {
+    let (a, b) = (a.to_coords().0, b.to_coords().0);
+    if a < b { (a, b) } else { (b, a) }
+}
+
+fn home_rank(color: PieceColor) -> u8 {
+    match color {
+        PieceColor::WHITE => 0,
+        PieceColor::BLACK => 7
+    }
+}
+
+/// # Starting and ending squares of king and rook taking part in castling
+///
+/// either of them may stay on its square in Chess960
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Castling {
+    king_side: bool,
+    king: (Square, Square),
+    rook: (Square, Square)
+}
+
+#[derive(Clone)]
 pub struct Board {
-    pieces: [Option<Piece>; 64]
+    pieces: [Option<Piece>; 64],
+    side_to_move: PieceColor,
+    /// castling is written as king capturing own rook and king and rooks may start on any file
+    chess960: bool,
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    hash: u64,
+    colors: [Bitboard; 2],
+    types: [Bitboard; 6]
 }
 
 impl Board {
@@ -35,7 +124,15 @@ impl Board {
         const INIT: Option<Piece> = None;
 
         Board {
-            pieces: [INIT; 64]
+            pieces: [INIT; 64],
+            side_to_move: PieceColor::WHITE,
+            chess960: false,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            hash: 0,
+            colors: [Bitboard::EMPTY; 2],
+            types: [Bitboard::EMPTY; 6]
         }
     
     }
@@ -56,32 +153,109 @@ impl Board {
     pub fn new() -> Board {
         let mut board = Board::new_clear();
 
-        board.pieces[Square::new(0, 0).to_index()] = Some(Piece::new(PieceType::Rook,   PieceColor::WHITE));
-        board.pieces[Square::new(1, 0).to_index()] = Some(Piece::new(PieceType::Knight, PieceColor::WHITE));
-        board.pieces[Square::new(2, 0).to_index()] = Some(Piece::new(PieceType::Bishop, PieceColor::WHITE));
-        board.pieces[Square::new(3, 0).to_index()] = Some(Piece::new(PieceType::Queen,  PieceColor::WHITE));
-        board.pieces[Square::new(4, 0).to_index()] = Some(Piece::new(PieceType::King,   PieceColor::WHITE));
-        board.pieces[Square::new(5, 0).to_index()] = Some(Piece::new(PieceType::Bishop, PieceColor::WHITE));
-        board.pieces[Square::new(6, 0).to_index()] = Some(Piece::new(PieceType::Knight, PieceColor::WHITE));
-        board.pieces[Square::new(7, 0).to_index()] = Some(Piece::new(PieceType::Rook,   PieceColor::WHITE));
-
-        board.pieces[Square::new(0, 7).to_index()] = Some(Piece::new(PieceType::Rook,   PieceColor::BLACK));
-        board.pieces[Square::new(1, 7).to_index()] = Some(Piece::new(PieceType::Knight, PieceColor::BLACK));
-        board.pieces[Square::new(2, 7).to_index()] = Some(Piece::new(PieceType::Bishop, PieceColor::BLACK));
-        board.pieces[Square::new(3, 7).to_index()] = Some(Piece::new(PieceType::Queen,  PieceColor::BLACK));
-        board.pieces[Square::new(4, 7).to_index()] = Some(Piece::new(PieceType::King,   PieceColor::BLACK));
-        board.pieces[Square::new(5, 7).to_index()] = Some(Piece::new(PieceType::Bishop, PieceColor::BLACK));
-        board.pieces[Square::new(6, 7).to_index()] = Some(Piece::new(PieceType::Knight, PieceColor::BLACK));
-        board.pieces[Square::new(7, 7).to_index()] = Some(Piece::new(PieceType::Rook,   PieceColor::BLACK));
+        board.replace_piece(Square::new(0, 0).to_index(), Some(Piece::new(PieceType::Rook,   PieceColor::WHITE)));
+        board.replace_piece(Square::new(1, 0).to_index(), Some(Piece::new(PieceType::Knight, PieceColor::WHITE)));
+        board.replace_piece(Square::new(2, 0).to_index(), Some(Piece::new(PieceType::Bishop, PieceColor::WHITE)));
+        board.replace_piece(Square::new(3, 0).to_index(), Some(Piece::new(PieceType::Queen,  PieceColor::WHITE)));
+        board.replace_piece(Square::new(4, 0).to_index(), Some(Piece::new(PieceType::King,   PieceColor::WHITE)));
+        board.replace_piece(Square::new(5, 0).to_index(), Some(Piece::new(PieceType::Bishop, PieceColor::WHITE)));
+        board.replace_piece(Square::new(6, 0).to_index(), Some(Piece::new(PieceType::Knight, PieceColor::WHITE)));
+        board.replace_piece(Square::new(7, 0).to_index(), Some(Piece::new(PieceType::Rook,   PieceColor::WHITE)));
+
+        board.replace_piece(Square::new(0, 7).to_index(), Some(Piece::new(PieceType::Rook,   PieceColor::BLACK)));
+        board.replace_piece(Square::new(1, 7).to_index(), Some(Piece::new(PieceType::Knight, PieceColor::BLACK)));
+        board.replace_piece(Square::new(2, 7).to_index(), Some(Piece::new(PieceType::Bishop, PieceColor::BLACK)));
+        board.replace_piece(Square::new(3, 7).to_index(), Some(Piece::new(PieceType::Queen,  PieceColor::BLACK)));
+        board.replace_piece(Square::new(4, 7).to_index(), Some(Piece::new(PieceType::King,   PieceColor::BLACK)));
+        board.replace_piece(Square::new(5, 7).to_index(), Some(Piece::new(PieceType::Bishop, PieceColor::BLACK)));
+        board.replace_piece(Square::new(6, 7).to_index(), Some(Piece::new(PieceType::Knight, PieceColor::BLACK)));
+        board.replace_piece(Square::new(7, 7).to_index(), Some(Piece::new(PieceType::Rook,   PieceColor::BLACK)));
 
         for i in 0..8 {
-            board.pieces[Square::new(i, 1).to_index()] = Some(Piece::new(PieceType::Pawn, PieceColor::WHITE));
-            board.pieces[Square::new(i, 6).to_index()] = Some(Piece::new(PieceType::Pawn, PieceColor::BLACK));
+            board.replace_piece(Square::new(i, 1).to_index(), Some(Piece::new(PieceType::Pawn, PieceColor::WHITE)));
+            board.replace_piece(Square::new(i, 6).to_index(), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
         }
 
+        board.hash = board.compute_hash();
         board
     }
 
+    /// # Creates Chess960 starting position with given Scharnagl number (0 - 959)
+    ///
+    /// number 518 is the standard starting position,
+    /// panics when number is out of range
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// assert_eq!(Board::new_chess960(518).to_fen(), Board::new().to_fen());
+    /// assert_eq!(Board::new_chess960(0).to_fen(), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1");
+    /// assert!(Board::new_chess960(0).is_chess960());
+    /// ```
+    pub fn new_chess960(id: u16) -> Board {
+        assert!(id < 960, "Chess960 position number has to be less than 960");
+
+        // files of both knights for every value of the last digit
+        const KNIGHTS: [(usize, usize); 10] = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)];
+
+        let mut rank: [Option<PieceType>; 8] = [None; 8];
+        let mut n = id as usize;
+
+        rank[n % 4 * 2 + 1] = Some(PieceType::Bishop);
+        n /= 4;
+        rank[n % 4 * 2] = Some(PieceType::Bishop);
+        n /= 4;
+
+        // remaining pieces are placed on n-th empty square
+        let mut place = |piece_type, n: usize| {
+            let x = (0..8).filter(|x| rank[*x].is_none()).nth(n).unwrap();
+            rank[x] = Some(piece_type);
+        };
+
+        place(PieceType::Queen, n % 6);
+        n /= 6;
+
+        let (first, second) = KNIGHTS[n];
+        place(PieceType::Knight, second);
+        place(PieceType::Knight, first);
+
+        for piece_type in [PieceType::Rook, PieceType::King, PieceType::Rook] {
+            place(piece_type, 0);
+        }
+
+        let mut board = Board::new_clear();
+        board.chess960 = true;
+
+        for (x, piece_type) in rank.into_iter().enumerate() {
+            let piece_type = piece_type.unwrap();
+
+            board.replace_piece(Square::new(x as u8, 0).to_index(), Some(Piece::new(piece_type, PieceColor::WHITE)));
+            board.replace_piece(Square::new(x as u8, 1).to_index(), Some(Piece::new(PieceType::Pawn, PieceColor::WHITE)));
+            board.replace_piece(Square::new(x as u8, 6).to_index(), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
+            board.replace_piece(Square::new(x as u8, 7).to_index(), Some(Piece::new(piece_type, PieceColor::BLACK)));
+        }
+
+        board.hash = board.compute_hash();
+        board
+    }
+
+    /// # Returns true if castling is written as king capturing own rook, like in Chess960
+    pub fn is_chess960(&self) -> bool {
+        self.chess960
+    }
+
+    /// # Sets whether castling is written as king capturing own rook
+    ///
+    /// boards created from FEN with king or castling rook off their standard squares are switched on automatically,
+    /// standard castling moves of king by two squares are no longer accepted when on,
+    /// castling rights may change, as outside of Chess960 only king on e-file and rooks in corners can castle
+    ///
+    pub fn set_chess960(&mut self, chess960: bool) {
+        self.hash ^= self.state_hash();
+        self.chess960 = chess960;
+        self.hash ^= self.state_hash();
+    }
+
     // basic board state getter functions
     /// # Returns piece at given index
     fn get_piece_at_index(&self, index: usize) -> Option<&Piece> {
@@ -94,75 +268,232 @@ impl Board {
         self.get_piece_at_index(square.to_index())
     }
 
-    // move simulating board state getter functions
-    /// # Returns piece at given index after simulating move
-    fn get_piece_at_index_after_move(&self, index: usize, sm: Option<Move>) -> Option<&Piece> {
-        match sm {
-            Some(sm) => {
-                if index == sm.start().to_index() {
-                    None
-                } else if index == sm.end().to_index() {
-                    self.get_piece_at_index(sm.start().to_index())    
-                } else {
-                    self.get_piece_at_index(index)
-                }
-            },
-            None => self.get_piece_at_index(index)
+    /// # Returns color of player who makes next move
+    ///
+    /// ```
+    /// # use chess_api::movement::{Move, Square};
+    /// # use chess_api::piece::PieceColor;
+    /// # use chess_api::board::Board;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// assert_eq!(board.side_to_move(), PieceColor::WHITE);
+    /// assert!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok()); // e4
+    /// assert_eq!(board.side_to_move(), PieceColor::BLACK);
+    /// ```
+    pub fn side_to_move(&self) -> PieceColor {
+        self.side_to_move
+    }
+
+    /// # Returns number of halfmoves since last capture or pawn move
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    /// # Returns number of current full move
+    ///
+    /// starts at 1 and is incremented after black's move
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    /// # Returns square skipped by pawn's two square move in last turn
+    ///
+    /// ```
+    /// # use chess_api::movement::{Move, Square};
+    /// # use chess_api::board::Board;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// assert!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok()); // e4
+    /// assert_eq!(board.en_passant(), Some(Square::new(4, 2)));
+    ///
+    /// assert!(board.perform_move(Move::new(Square::new(6, 7), Square::new(5, 5))).is_ok()); // Nf6
+    /// assert_eq!(board.en_passant(), None);
+    /// ```
+    pub fn en_passant(&self) -> Option<Square> {
+        self.en_passant
+    }
+
+    /// # Returns true if king and rook needed for given castling have not moved
+    fn has_castling_right(&self, color: PieceColor, king_side: bool) -> bool {
+        self.castling_rook(color, king_side).is_some()
+    }
+
+    /// # Returns square of rook which can castle on given side, the outermost unmoved one
+    ///
+    /// king has to be unmoved too, outside of Chess960 only king on e-file and rooks in corners can castle
+    fn castling_rook(&self, color: PieceColor, king_side: bool) -> Option<Square> {
+        let rank = home_rank(color);
+        let king = self.king_square(color)?;
+        let king_x = king.to_coords().0;
+
+        if king.to_coords().1 != rank || self.get_piece(king).unwrap().moved() || (!self.chess960 && king_x != 4) {
+            return None;
+        }
+
+        let unmoved_rook = |x| self.get_piece(Square::new(x, rank)).is_some_and(|piece| piece.piece_type() == PieceType::Rook && piece.color() == color && !piece.moved());
+        let corner = if king_side { 7 } else { 0 };
+
+        if !self.chess960 {
+            return Some(Square::new(corner, rank)).filter(|_| unmoved_rook(corner));
         }
+
+        let mut x = corner;
+
+        while x != king_x {
+            if unmoved_rook(x) {
+                return Some(Square::new(x, rank));
+            }
+
+            x = if king_side { x - 1 } else { x + 1 };
+        }
+
+        None
     }
 
-    /// # Returns piece at given square after simulating move
-    fn get_piece_after_move(&self, square: Square, sm: Option<Move>) -> Option<&Piece> {
-        self.get_piece_at_index_after_move(square.to_index(), sm)
+    /// # Returns king's and rook's part of the move if given move is castling
+    ///
+    /// castling is written as king's two square move towards unmoved rook,
+    /// in Chess960 as king's move to square of unmoved rook
+    fn castling(&self, m: Move) -> Option<Castling> {
+        let ((sx, sy), (ex, ey)) = m.to_coords();
+        let king = self.get_piece(m.start())?;
+
+        if king.piece_type() != PieceType::King || king.moved() || sy != home_rank(king.color()) || ey != sy || m.promotion().is_some() {
+            return None;
+        }
+
+        if !self.chess960 && m.to_deltas().0 != 2 {
+            return None;
+        }
+
+        let king_side = ex > sx;
+        let rook = self.castling_rook(king.color(), king_side)?;
+
+        if self.chess960 && rook != m.end() {
+            return None;
+        }
+
+        let (king_x, rook_x) = if king_side { (6, 5) } else { (2, 3) };
+
+        Some(Castling {
+            king_side,
+            king: (m.start(), Square::new(king_x, sy)),
+            rook: (rook, Square::new(rook_x, sy))
+        })
     }
 
+    /// # Returns square of pawn captured if given move is en passant
+    fn en_passant_capture_square(&self, m: Move) -> Option<Square> {
+        let target = self.en_passant?;
+        let pawn = self.get_piece(m.start())?;
+
+        if pawn.piece_type() != PieceType::Pawn || m.end() != target || m.to_deltas() != (1, 1) {
+            return None;
+        }
+
+        let captured = Square::new(m.end().to_coords().0, m.start().to_coords().1);
 
+        match self.get_piece(captured) {
+            Some(piece) if piece.piece_type() == PieceType::Pawn && piece.color() != pawn.color() => Some(captured),
+            _ => None
+        }
+    }
 
+    /// # Returns true if given move takes pawn to the last rank
+    fn is_promoting_move(&self, m: Move) -> bool {
+        match self.get_piece(m.start()) {
+            Some(piece) if piece.piece_type() == PieceType::Pawn => {
+                let last_rank = match piece.color() {
+                    PieceColor::WHITE => 7,
+                    PieceColor::BLACK => 0
+                };
+
+                m.end().to_coords().1 == last_rank
+            },
+            _ => false
+        }
+    }
 
     // move possibility checks
-    fn check_move_possibility_after_move(&self, m: Move, sm: Option<Move>) -> Result<(), MoveFailReason> {
+    /// # Checks that squares king and rook pass are empty and squares king passes are not attacked
+    fn check_castling_possibility(&self, castling: Castling) -> Result<(), MoveFailReason> {
+        let ((king_start, king_end), (rook_start, rook_end)) = (castling.king, castling.rook);
+        let color = self.get_piece(king_start).unwrap().color();
+        let rank = king_start.to_coords().1;
+
+        let files = [king_start, king_end, rook_start, rook_end].map(|square| square.to_coords().0);
+        let (first, last) = (*files.iter().min().unwrap(), *files.iter().max().unwrap());
+
+        let blocked = (first..=last)
+            .map(|x| Square::new(x, rank))
+            .any(|square| square != king_start && square != rook_start && self.get_piece(square).is_some());
+
+        if blocked {
+            return Err(MoveFailReason::IllegalMove);
+        }
+
+        // rook may shield squares of king's path before castling, but not after it
+        let occupied = self.occupied() & !Bitboard::from_square(king_start) & !Bitboard::from_square(rook_start);
+        let (first, last) = min_max_files(king_start, king_end);
+
+        if (first..=last).any(|x| !self.attackers_to(Square::new(x, rank), !color, occupied).is_empty()) {
+            Err(MoveFailReason::KingAttacked)
+        } else { Ok(()) }
+    }
+
+    /// # Checks move without taking side to move into account
+    fn check_move_legality(&self, m: Move) -> Result<(), MoveFailReason> {
         let (src, dst) = m.to_squares();
 
-        let source_piece = self.get_piece_after_move(src, sm);
-        let destination_piece = self.get_piece_after_move(dst, sm);
+        let source_piece = match self.get_piece(src) {
+            Some(piece) => piece,
+            None => return Err(MoveFailReason::NoSourcePiece)
+        };
 
-        if let Some(source_piece) = source_piece {
-            let dest_ocuppied = if let Some(destination_piece) = destination_piece {
-                if source_piece.color() == destination_piece.color() {
-                    return Err(MoveFailReason::FriendlyFire);
-                }
-                true
-            } else { false };
+        // castling in Chess960 looks like capturing own rook
+        if let Some(castling) = self.castling(m) {
+            return self.check_castling_possibility(castling);
+        }
 
-            let (can_move, validate_block) = source_piece.can_move_to(m, dest_ocuppied);
+        if self.get_piece(dst).is_some_and(|piece| piece.color() == source_piece.color()) {
+            return Err(MoveFailReason::FriendlyFire);
+        }
 
-            let move_possible = if validate_block && can_move {
-                LineMovement::from(m).all(|pos| self.get_piece_after_move(pos, sm).is_none())
-            } else { can_move };
+        if !self.piece_reach(src).contains(dst) {
+            return Err(MoveFailReason::IllegalMove);
+        }
 
-            if move_possible {
-                if sm.is_some() {
-                    Ok(())
-                } else if self.is_king_attacked_after_move(source_piece.color(), Some(m)) {
-                    Err(MoveFailReason::KingAttacked)
-                } else { Ok(()) }
-            } else { Err(MoveFailReason::IllegalMove) }
-        } else { Err(MoveFailReason::NoSourcePiece) }
+        match (self.is_promoting_move(m), m.promotion()) {
+            (true, None) => return Err(MoveFailReason::PromotionRequired),
+            (true, Some(promotion)) if !PieceType::PROMOTIONS.contains(&promotion) => return Err(MoveFailReason::IllegalMove),
+            (false, Some(_)) => return Err(MoveFailReason::IllegalMove),
+            _ => ()
+        }
+
+        if self.leaves_king_attacked(m) {
+            Err(MoveFailReason::KingAttacked)
+        } else { Ok(()) }
     }
 
     /// # 
     ///
     /// ```
     /// # use chess_api::movement::{Move, Square};
-    /// # use chess_api::board::Board;
+    /// # use chess_api::board::{Board, MoveFailReason};
     ///
     /// # let board = Board::new();
     ///
     /// assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 1), Square::new(1, 3))).is_ok(), true);
     /// assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 0), Square::new(1, 3))).is_ok(), false);
+    /// assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 6), Square::new(1, 4))).err().unwrap(), MoveFailReason::WrongTurn);
     /// ```
     pub fn check_move_possibility(&self, m: Move) -> Result<(), MoveFailReason> {
-        self.check_move_possibility_after_move(m, None) 
+        match self.get_piece(m.start()) {
+            Some(piece) if piece.color() != self.side_to_move => Err(MoveFailReason::WrongTurn),
+            _ => self.check_move_legality(m)
+        }
     }
 
 
@@ -175,12 +506,62 @@ impl Board {
     /// not for moving pieces during game
     ///
     pub fn set(&mut self, square: Square, piece: Option<Piece>) {
-        self.pieces[square.to_index()] = piece; 
+        self.hash ^= self.state_hash();
+        self.replace_piece(square.to_index(), piece);
+        self.hash ^= self.state_hash();
+    }
+
+    /// # Sets color of player who makes next move
+    ///
+    /// should only be used for setting up custom positions
+    ///
+    pub fn set_side_to_move(&mut self, color: PieceColor) {
+        self.hash ^= self.state_hash();
+        self.side_to_move = color;
+        self.hash ^= self.state_hash();
+    }
+
+    /// # Puts piece at given index and returns replaced piece, keeping hash and bitboards up to date
+    fn replace_piece(&mut self, index: usize, piece: Option<Piece>) -> Option<Piece> {
+        let square = Square::from_index(index);
+        let bit = Bitboard::from_square(square);
+
+        if let Some(piece) = piece.as_ref() {
+            self.hash ^= zobrist::piece_key(square, piece);
+            self.colors[piece.color() as usize] ^= bit;
+            self.types[piece.piece_type() as usize] ^= bit;
+        }
+
+        let replaced = std::mem::replace(&mut self.pieces[index], piece);
+
+        if let Some(replaced) = replaced.as_ref() {
+            self.hash ^= zobrist::piece_key(square, replaced);
+            self.colors[replaced.color() as usize] ^= bit;
+            self.types[replaced.piece_type() as usize] ^= bit;
+        }
+
+        replaced
+    }
+
+    /// # Moves piece without any checks and marks it as moved
+    fn relocate_piece(&mut self, m: Move) {
+        let mut piece = self.replace_piece(m.start().to_index(), None).unwrap();
+
+        piece.move_piece();
+        self.replace_piece(m.end().to_index(), Some(piece));
     }
 
     // advanced board state changers
     /// # Performs move after checking if it is possible
     ///
+    /// castling is performed by moving king two squares towards the rook,
+    /// rook is relocated automatically
+    ///
+    /// en passant is performed by moving pawn to the square returned by `en_passant`,
+    /// captured pawn is removed automatically
+    ///
+    /// pawn reaching the last rank has to be given promotion piece with `Move::new_promotion`
+    ///
     /// ```
     /// # use chess_api::movement::{Move, Square};
     /// # use chess_api::piece::{PieceType, PieceColor};
@@ -195,40 +576,207 @@ impl Board {
     /// assert!(board.get_piece(Square::new(1, 1)).is_none());
     /// ```
     pub fn perform_move(&mut self, m: Move) -> Result<PostMoveState, MoveFailReason> {
+        self.perform_move_with_undo(m).map(|(state, _)| state)
+    }
+
+    /// # Performs move after checking if it is possible and returns record needed to take it back
+    ///
+    /// ```
+    /// # use chess_api::movement::{Move, Square};
+    /// # use chess_api::board::Board;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// let (_, undo) = board.perform_move_with_undo(Move::new(Square::new(4, 1), Square::new(4, 3))).unwrap();
+    /// board.undo_move(undo);
+    ///
+    /// assert_eq!(board.to_fen(), Board::new().to_fen());
+    /// ```
+    pub fn perform_move_with_undo(&mut self, m: Move) -> Result<(PostMoveState, MoveUndo), MoveFailReason> {
         match self.check_move_possibility(m) {
             Ok(_) => {
-                let src = m.start().to_index();
-                let dst = m.end().to_index();
-
-                self.pieces[dst] = self.pieces[src].take();
-                self.pieces[dst].as_mut().unwrap().move_piece();
-
-                let next_color = !self.pieces[dst].as_ref().unwrap().color();
-                let king_attacked = self.is_king_attacked(next_color);
-                let has_moves = self.all_possible_moves(Some(next_color)).next().is_some();
-
-                if king_attacked && has_moves {
-                    Ok(PostMoveState::Check)
-                } else if king_attacked {
-                    Ok(PostMoveState::Checkmate)
-                } else if has_moves {
-                    Ok(PostMoveState::Normal)
-                } else {
-                    Ok(PostMoveState::Stelemate)
-                }
+                let undo = self.apply_move(m);
+                Ok((self.post_move_state(), undo))
             },
             Err(e) => Err(e)
         }
     }
 
-    // iterators
-    fn squares_after_move(&self, sm: Option<Move>) -> impl Iterator<Item = (Square, Option<&Piece>)> {
-        (0..64).map(|index| Square::from_index(index)).map(move |square| (square, self.get_piece_after_move(square, sm)))
+    /// # Takes back move described by given record
+    ///
+    /// record has to come from the last move performed on this board
+    ///
+    pub fn undo_move(&mut self, undo: MoveUndo) {
+        let MoveUndo { m, piece, captured, castling, en_passant, halfmove_clock, fullmove_number, hash } = undo;
+
+        match castling {
+            Some(castling) => {
+                self.replace_piece(castling.king.1.to_index(), None);
+                self.replace_piece(castling.rook.1.to_index(), None);
+                self.replace_piece(castling.rook.0.to_index(), Some(Piece::new(PieceType::Rook, piece.color())));
+                self.replace_piece(castling.king.0.to_index(), Some(piece));
+            },
+            None => {
+                self.replace_piece(m.end().to_index(), None);
+                self.replace_piece(m.start().to_index(), Some(piece));
+            }
+        }
+
+        if let Some((square, piece)) = captured {
+            self.replace_piece(square.to_index(), Some(piece));
+        }
+
+        self.side_to_move = !self.side_to_move;
+        self.en_passant = en_passant;
+        self.halfmove_clock = halfmove_clock;
+        self.fullmove_number = fullmove_number;
+        self.hash = hash;
+    }
+
+    /// # Performs move without any checks and returns record needed to take it back
+    ///
+    /// move has to be legal, for example one returned by `generate_legal`,
+    /// unlike `perform_move_with_undo` state of the game is not computed
+    ///
+    /// ```
+    /// # use chess_api::board::{Board, MoveFilter};
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// for m in board.generate_legal(MoveFilter::All) {
+    ///     let undo = board.apply_move(m);
+    ///     board.undo_move(undo);
+    /// }
+    ///
+    /// assert_eq!(board.hash(), Board::new().hash());
+    /// ```
+    pub fn apply_move(&mut self, m: Move) -> MoveUndo {
+        let captured_square = self.en_passant_capture_square(m).unwrap_or(m.end());
+        let castling = self.castling(m);
+
+        let undo = MoveUndo {
+            m,
+            piece: self.pieces[m.start().to_index()].clone().unwrap(),
+            captured: self.pieces[captured_square.to_index()].clone().filter(|_| castling.is_none()).map(|piece| (captured_square, piece)),
+            castling,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            hash: self.hash
+        };
+
+        self.hash ^= self.state_hash();
+
+        if let Some(castling) = undo.castling {
+            // both pieces are lifted first, as either may end on square of the other
+            let mut king = self.replace_piece(castling.king.0.to_index(), None).unwrap();
+            let mut rook = self.replace_piece(castling.rook.0.to_index(), None).unwrap();
+
+            king.move_piece();
+            rook.move_piece();
+
+            self.replace_piece(castling.king.1.to_index(), Some(king));
+            self.replace_piece(castling.rook.1.to_index(), Some(rook));
+        } else {
+            if undo.captured.is_some() {
+                self.replace_piece(captured_square.to_index(), None);
+            }
+
+            self.relocate_piece(m);
+        }
+
+        let dst = m.end().to_index();
+
+        if let Some(promotion) = m.promotion() {
+            let mut piece = Piece::new(promotion, undo.piece.color());
+
+            piece.move_piece();
+            self.replace_piece(dst, Some(piece));
+        }
+
+        let pawn_moved = undo.piece.piece_type() == PieceType::Pawn;
+
+        self.en_passant = if pawn_moved && m.to_deltas().1 == 2 {
+            LineMovement::from(m).next()
+        } else { None };
+
+        self.halfmove_clock = if pawn_moved || undo.captured.is_some() { 0 } else { self.halfmove_clock + 1 };
+
+        if self.side_to_move == PieceColor::BLACK {
+            self.fullmove_number += 1;
+        }
+
+        self.side_to_move = !self.side_to_move;
+        self.hash ^= self.state_hash();
+
+        undo
+    }
+
+    /// # Returns state of the game for player who makes next move
+    ///
+    /// repetitions are not taken into account, as board does not remember previous positions
+    ///
+    fn post_move_state(&self) -> PostMoveState {
+        let next_color = self.side_to_move;
+        let king_attacked = self.is_king_attacked(next_color);
+        let has_moves = self.all_possible_moves(Some(next_color)).next().is_some();
+
+        if !has_moves {
+            return if king_attacked { PostMoveState::Checkmate } else { PostMoveState::Stelemate };
+        }
+
+        if self.is_insufficient_material() {
+            PostMoveState::Draw(DrawReason::InsufficientMaterial)
+        } else if self.halfmove_clock >= 150 {
+            PostMoveState::Draw(DrawReason::SeventyFiveMoveRule)
+        } else if king_attacked {
+            PostMoveState::Check
+        } else {
+            PostMoveState::Normal
+        }
     }
 
+    /// # Returns true if neither player can checkmate
+    ///
+    /// that is when only kings are left, with at most one knight or any number of bishops on same colored squares
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// assert!(Board::from_fen("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1").unwrap().is_insufficient_material());
+    /// assert!(Board::from_fen("4k3/8/8/4b3/8/8/8/2B1K3 w - - 0 1").unwrap().is_insufficient_material());
+    /// assert!(!Board::from_fen("4k3/8/8/3b4/8/8/8/2B1K3 w - - 0 1").unwrap().is_insufficient_material());
+    /// assert!(!Board::from_fen("4k3/8/8/8/8/8/8/1NN1K3 w - - 0 1").unwrap().is_insufficient_material());
+    /// ```
+    pub fn is_insufficient_material(&self) -> bool {
+        let others: Vec<(Square, &Piece)> = self.pieces(None).filter(|(_, piece)| piece.piece_type() != PieceType::King).collect();
+
+        let square_color = |square: Square| {
+            let (x, y) = square.to_coords();
+            (x + y) % 2
+        };
+
+        match others[..] {
+            [] => true,
+            [(_, piece)] => matches!(piece.piece_type(), PieceType::Knight | PieceType::Bishop),
+            [(first, _), ..] => others.iter().all(|(square, piece)| piece.piece_type() == PieceType::Bishop && square_color(*square) == square_color(first))
+        }
+    }
+
+    /// # Returns draw which can be claimed by player who makes next move
+    ///
+    /// repetitions are not taken into account, see `Game::claimable_draw`
+    ///
+    pub fn claimable_draw(&self) -> Option<DrawReason> {
+        if self.halfmove_clock >= 100 {
+            Some(DrawReason::FiftyMoveRule)
+        } else { None }
+    }
+
+    // iterators
     /// # Returns iterator over every square on the board
     pub fn squares(&self) -> impl Iterator<Item = (Square, Option<&Piece>)> {
-        self.squares_after_move(None)
+        (0..64).map(Square::from_index).map(move |square| (square, self.get_piece(square)))
     }
 
     /// # Returns iterator over every piece on the board
@@ -242,26 +790,16 @@ impl Board {
     ///     println!("{:?} => {:?}", square, piece);
     /// }
     /// ```
-    fn pieces_after_move(&self, color: Option<PieceColor>, sm: Option<Move>) -> impl Iterator<Item = (Square, &Piece)> {
-        self.squares_after_move(sm).filter_map(|square| match square.1 {
-            Some(piece) => Some((square.0, piece)),
-            None => None
-        }).filter(move |piece| match color {
-            Some(color) => piece.1.color() == color,
-            None => true
-        })
-    }
-
     pub fn pieces(&self, color: Option<PieceColor>) -> impl Iterator<Item = (Square, &Piece)> {
-        self.pieces_after_move(color, None)
-    }
+        let set = match color {
+            Some(color) => self.colors[color as usize],
+            None => self.occupied()
+        };
 
-    // advanced board state getters
-    /// # Returns true if given square is attacked by given player after simulating move
-    fn is_square_attacked_after_move(&self, square: Square, color: PieceColor, sm: Option<Move>) -> bool {
-        self.pieces_after_move(Some(color), sm).filter(|(start, _)| *start != square).any(|(start, _)| self.check_move_possibility_after_move(Move::new(start, square), sm).is_ok())
+        set.squares().map(move |square| (square, self.get_piece(square).unwrap()))
     }
 
+    // advanced board state getters
     /// # Returns true if given square is attacked by given player
     ///
     /// will return false if attacked only by allied piece
@@ -281,14 +819,11 @@ impl Board {
     /// assert_eq!(board.is_square_attacked(Square::new(3, 1), PieceColor::BLACK), false);
     /// ```
     pub fn is_square_attacked(&self, square: Square, color: PieceColor) -> bool {
-        self.is_square_attacked_after_move(square, color, None)
-    }
+        if self.colors[color as usize].contains(square) {
+            return false;
+        }
 
-    /// # If king is attacked returns true
-    fn is_king_attacked_after_move(&self, color: PieceColor, sm: Option<Move>) -> bool {
-        if let Some((square, _)) = self.pieces_after_move(Some(color), sm).find(|(_, piece)| piece.piece_type() == PieceType::King) {
-            self.is_square_attacked_after_move(square, !color, sm)
-        } else { false }
+        !self.attackers_to(square, color, self.occupied()).is_empty()
     }
 
     /// # If king is attacked returns true
@@ -310,12 +845,16 @@ impl Board {
     /// assert_eq!(board.is_king_attacked(PieceColor::BLACK), true);
     /// ```
     pub fn is_king_attacked(&self, color: PieceColor) -> bool {
-        self.is_king_attacked_after_move(color, None)
+        match self.king_square(color) {
+            Some(square) => self.is_square_attacked(square, !color),
+            None => false
+        }
     }
 
     /// # Returns iterator for every possible move from given square
     ///
-    /// move order is not defined and may change in future
+    /// move order is not defined and may change in future,
+    /// side to move is not taken into account
     ///
     /// ```
     /// # use chess_api::board::Board;
@@ -330,19 +869,23 @@ impl Board {
     /// assert_eq!(board.all_possible_moves_from_square(Square::new(4, 3)).count(), 1);
     /// assert_eq!(board.all_possible_moves_from_square(Square::new(3, 1)).count(), 2);
     /// ```
-
     pub fn all_possible_moves_from_square<'a>(&'a self, start: Square) -> impl Iterator<Item = Move> + 'a {
-        // todo optimize, piece should give subset of board's squares to test for move possibility
-        // fx: for pawn we are testing all 64 destination squares but we should only test 4
-        self.squares()
-            .filter_map(move |(end, _)| if end == start { None } else { Some(end) })
-            .map(move |end| Move::new(start, end))
-            .filter(|m| self.check_move_possibility(*m).is_ok())
+        self.piece_reach(start).squares()
+            .flat_map(move |end| {
+                let promotions: &[PieceType] = if self.is_promoting_move(Move::new(start, end)) { &PieceType::PROMOTIONS } else { &[] };
+
+                promotions.iter()
+                    .map(move |promotion| Move::new_promotion(start, end, *promotion))
+                    .chain(Some(Move::new(start, end)).filter(|_| promotions.is_empty()))
+            })
+            .filter(move |m| !self.leaves_king_attacked(*m))
+            .chain(self.castling_moves(start))
     }
 
     /// # Returns iterator for every possoble move by given color
     ///
-    /// move order is not defined and may change in future
+    /// move order is not defined and may change in future,
+    /// side to move is not taken into account
     ///
     /// ```
     /// # use chess_api::board::Board;
@@ -356,3 +899,9 @@ impl Board {
         self.pieces(color).flat_map(|(square, _)| self.all_possible_moves_from_square(square))
     }
 }
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::new()
+    }
+}
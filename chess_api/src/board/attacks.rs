@@ -0,0 +1,174 @@
+use crate::bitboard::*;
+use crate::movement::{LineMovement, Square};
+use crate::piece::{PieceColor, PieceType};
+
+use super::Board;
+
+
+/// # Piece which cannot leave the line between its king and enemy sliding piece
+///
+/// returned by `Board::pins`
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Pin {
+    pinned: Square,
+    pinner: Square,
+    ray: Bitboard
+}
+
+impl Pin {
+    /// # Returns square of pinned piece
+    pub fn pinned(&self) -> Square {
+        self.pinned
+    }
+
+    /// # Returns square of enemy piece pinning
+    pub fn pinner(&self) -> Square {
+        self.pinner
+    }
+
+    /// # Returns squares pinned piece can stand on without exposing its king
+    ///
+    /// ray goes from the square next to the king up to and including pinner's square
+    ///
+    pub fn ray(&self) -> Bitboard {
+        self.ray
+    }
+}
+
+impl Board {
+    /// # Returns pieces giving check to king of given color
+    ///
+    /// empty if there is no king of given color
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// // double check from rook on e8 and knight on d3
+    /// let board = Board::from_fen("4r2k/8/8/8/8/3n4/8/4K3 w - - 0 1").unwrap();
+    /// let checkers: Vec<Square> = board.checkers(PieceColor::WHITE).squares().collect();
+    ///
+    /// assert_eq!(checkers, [Square::new(3, 2), Square::new(4, 7)]);
+    /// assert!(board.checkers(PieceColor::BLACK).is_empty());
+    /// ```
+    pub fn checkers(&self, color: PieceColor) -> Bitboard {
+        match self.king_square(color) {
+            Some(king) => self.attackers_to(king, !color, self.occupied()),
+            None => Bitboard::EMPTY
+        }
+    }
+
+    /// # Returns pieces of given color pinned to their king
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// // knight on e3 is pinned by rook on e6, bishop on c3 is shielded by pawn on b4
+    /// let board = Board::from_fen("7k/8/4r3/q7/1P6/2B1N3/8/4K3 w - - 0 1").unwrap();
+    /// let pins = board.pins(PieceColor::WHITE);
+    ///
+    /// assert_eq!(pins.len(), 1);
+    /// assert_eq!(pins[0].pinned(), Square::new(4, 2));
+    /// assert_eq!(pins[0].pinner(), Square::new(4, 5));
+    /// assert_eq!(pins[0].ray().squares().count(), 5); // e2 to e6
+    /// ```
+    pub fn pins(&self, color: PieceColor) -> Vec<Pin> {
+        let king = match self.king_square(color) {
+            Some(king) => king,
+            None => return Vec::new()
+        };
+
+        let enemy = !color;
+        let queens = self.pieces_of(enemy, PieceType::Queen);
+
+        let snipers = (rook_attacks(king, Bitboard::EMPTY) & (self.pieces_of(enemy, PieceType::Rook) | queens))
+            | (bishop_attacks(king, Bitboard::EMPTY) & (self.pieces_of(enemy, PieceType::Bishop) | queens));
+
+        snipers.squares().filter_map(|pinner| {
+            let between = LineMovement::new(king, pinner).fold(Bitboard::EMPTY, |set, square| set | Bitboard::from_square(square));
+            let blockers = between & self.occupied();
+
+            if blockers.count() == 1 && !(blockers & self.colors[color as usize]).is_empty() {
+                Some(Pin {
+                    pinned: blockers.first().unwrap(),
+                    pinner,
+                    ray: between | Bitboard::from_square(pinner)
+                })
+            } else { None }
+        }).collect()
+    }
+
+    /// # Returns every square attacked by given player
+    ///
+    /// squares occupied by player's own pieces are included when they are defended,
+    /// pawns attack only diagonally
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let board = Board::new();
+    /// let attacked = board.attacked_squares(PieceColor::WHITE);
+    ///
+    /// assert_eq!(attacked.count(), 22); // whole third rank and everything on first two ranks but a1 and h1
+    /// assert!(attacked.contains(Square::new(4, 2)));
+    /// assert!(!attacked.contains(Square::new(4, 3)));
+    /// ```
+    pub fn attacked_squares(&self, color: PieceColor) -> Bitboard {
+        let occupied = self.occupied();
+
+        self.colors[color as usize].squares().fold(Bitboard::EMPTY, |attacked, square| {
+            attacked | match self.get_piece(square).unwrap().piece_type() {
+                PieceType::Pawn => pawn_attacks(color, square),
+                PieceType::Knight => knight_attacks(square),
+                PieceType::Bishop => bishop_attacks(square, occupied),
+                PieceType::Rook => rook_attacks(square, occupied),
+                PieceType::Queen => queen_attacks(square, occupied),
+                PieceType::King => king_attacks(square)
+            }
+        })
+    }
+
+    /// # Returns pieces of given color attacking given square
+    ///
+    /// unlike `is_square_attacked`, square may be occupied by any piece
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.attackers(Square::new(5, 2), PieceColor::WHITE).count(), 3); // f3: e2, g2 and g1
+    /// assert!(board.attackers(Square::new(5, 2), PieceColor::BLACK).is_empty());
+    /// ```
+    pub fn attackers(&self, square: Square, color: PieceColor) -> Bitboard {
+        self.attackers_to(square, color, self.occupied())
+    }
+
+    /// # Returns pieces protecting piece at given square
+    ///
+    /// defenders have the same color as the piece, empty if square is empty
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.defenders(Square::new(4, 1)).count(), 4); // e2: king, queen, bishop and knight
+    /// assert!(board.defenders(Square::new(0, 0)).is_empty());
+    /// assert!(board.defenders(Square::new(4, 3)).is_empty());
+    /// ```
+    pub fn defenders(&self, square: Square) -> Bitboard {
+        match self.get_piece(square) {
+            Some(piece) => self.attackers(square, piece.color()),
+            None => Bitboard::EMPTY
+        }
+    }
+}
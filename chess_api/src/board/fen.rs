@@ -0,0 +1,354 @@
+use std::fmt;
+
+use super::{Board, home_rank};
+use crate::piece::*;
+use crate::movement::*;
+
+
+/// # Reason why FEN string could not be parsed
+#[derive(Debug, PartialEq)]
+pub enum FenError {
+    /// FEN has other number of fields than 6
+    FieldCount(usize),
+    /// piece placement has other number of ranks than 8
+    RankCount(usize),
+    /// rank with given number (1 - 8) does not describe exactly 8 squares
+    RankLength(u8),
+    /// unknown character in piece placement
+    InvalidPiece(char),
+    InvalidSideToMove(String),
+    InvalidCastling(String),
+    InvalidEnPassant(String),
+    InvalidHalfmoveClock(String),
+    InvalidFullmoveNumber(String)
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FenError::FieldCount(n) => write!(f, "expected 6 fields, found {}", n),
+            FenError::RankCount(n) => write!(f, "expected 8 ranks in piece placement, found {}", n),
+            FenError::RankLength(rank) => write!(f, "rank {} does not describe exactly 8 squares", rank),
+            FenError::InvalidPiece(c) => write!(f, "invalid character '{}' in piece placement", c),
+            FenError::InvalidSideToMove(s) => write!(f, "invalid side to move '{}'", s),
+            FenError::InvalidCastling(s) => write!(f, "invalid castling rights '{}'", s),
+            FenError::InvalidEnPassant(s) => write!(f, "invalid en passant square '{}'", s),
+            FenError::InvalidHalfmoveClock(s) => write!(f, "invalid halfmove clock '{}'", s),
+            FenError::InvalidFullmoveNumber(s) => write!(f, "invalid fullmove number '{}'", s)
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+fn piece_from_fen(c: char) -> Option<Piece> {
+    let color = if c.is_ascii_uppercase() { PieceColor::WHITE } else { PieceColor::BLACK };
+
+    let piece_type = match c.to_ascii_lowercase() {
+        'p' => PieceType::Pawn,
+        'r' => PieceType::Rook,
+        'n' => PieceType::Knight,
+        'b' => PieceType::Bishop,
+        'q' => PieceType::Queen,
+        'k' => PieceType::King,
+        _ => return None
+    };
+
+    Some(Piece::new(piece_type, color))
+}
+
+fn piece_to_fen(piece: &Piece) -> char {
+    match piece.color() {
+        PieceColor::WHITE => piece.piece_type().to_uci().to_ascii_uppercase(),
+        PieceColor::BLACK => piece.piece_type().to_uci()
+    }
+}
+
+fn pawn_rank(color: PieceColor) -> u8 {
+    match color {
+        PieceColor::WHITE => 1,
+        PieceColor::BLACK => 6
+    }
+}
+
+const CASTLING_RIGHTS: [(char, PieceColor, bool); 4] = [
+    ('K', PieceColor::WHITE, true),
+    ('Q', PieceColor::WHITE, false),
+    ('k', PieceColor::BLACK, true),
+    ('q', PieceColor::BLACK, false)
+];
+
+impl Board {
+    /// # Creates board from FEN string
+    ///
+    /// castling rights are stored as `moved` flags of kings and rooks,
+    /// pawns outside of their starting rank are marked as moved
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    ///
+    /// assert_eq!(board.side_to_move(), PieceColor::BLACK);
+    /// assert_eq!(board.en_passant(), Some(Square::new(4, 2)));
+    /// assert!(Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1").is_err());
+    /// ```
+    pub fn from_fen(fen: &str) -> Result<Board, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+
+        if fields.len() != 6 {
+            return Err(FenError::FieldCount(fields.len()));
+        }
+
+        let mut board = Board::from_fen_position(&fields[..4])?;
+
+        board.halfmove_clock = fields[4].parse().map_err(|_| FenError::InvalidHalfmoveClock(fields[4].to_string()))?;
+        board.fullmove_number = match fields[5].parse() {
+            Ok(n) if n > 0 => n,
+            _ => return Err(FenError::InvalidFullmoveNumber(fields[5].to_string()))
+        };
+
+        Ok(board)
+    }
+
+    /// # Creates board from first four FEN fields
+    fn from_fen_position(fields: &[&str]) -> Result<Board, FenError> {
+        let mut board = Board::new_clear();
+
+        board.parse_placement(fields[0])?;
+
+        board.side_to_move = match fields[1] {
+            "w" => PieceColor::WHITE,
+            "b" => PieceColor::BLACK,
+            other => return Err(FenError::InvalidSideToMove(other.to_string()))
+        };
+
+        board.parse_castling(fields[2])?;
+        board.parse_en_passant(fields[3])?;
+
+        board.hash = board.compute_hash();
+
+        Ok(board)
+    }
+
+    fn parse_placement(&mut self, placement: &str) -> Result<(), FenError> {
+        let ranks: Vec<&str> = placement.split('/').collect();
+
+        if ranks.len() != 8 {
+            return Err(FenError::RankCount(ranks.len()));
+        }
+
+        for (i, rank) in ranks.iter().enumerate() {
+            let y = 7 - i as u8;
+            let mut x = 0;
+
+            for c in rank.chars() {
+                match c {
+                    '1'..='8' => x += c as u8 - b'0',
+                    _ => {
+                        let piece = piece_from_fen(c).ok_or(FenError::InvalidPiece(c))?;
+
+                        if x >= 8 {
+                            return Err(FenError::RankLength(y + 1));
+                        }
+
+                        self.replace_piece(Square::new(x, y).to_index(), Some(piece));
+                        x += 1;
+                    }
+                }
+
+                if x > 8 {
+                    return Err(FenError::RankLength(y + 1));
+                }
+            }
+
+            if x != 8 {
+                return Err(FenError::RankLength(y + 1));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// # Parses castling rights, both X-FEN and Shredder-FEN are accepted
+    ///
+    /// `K` and `Q` stand for the outermost rook on given side of the king, file letters for rook on that file,
+    /// board is switched to Chess960 when file letters are used or king or rook are not on their standard squares
+    ///
+    fn parse_castling(&mut self, castling: &str) -> Result<(), FenError> {
+        let error = || FenError::InvalidCastling(castling.to_string());
+        let mut unmoved = vec![];
+        let mut rights = vec![];
+
+        if castling != "-" {
+            for c in castling.chars() {
+                let color = if c.is_ascii_uppercase() { PieceColor::WHITE } else { PieceColor::BLACK };
+                let rank = home_rank(color);
+                let has_piece = |x, piece_type| self.get_piece(Square::new(x, rank)).is_some_and(|piece| piece.piece_type() == piece_type && piece.color() == color);
+
+                let king_x = (0..8).find(|x| has_piece(*x, PieceType::King)).ok_or_else(error)?;
+
+                let rook_x = match CASTLING_RIGHTS.iter().find(|(right, _, _)| *right == c) {
+                    Some(&(_, _, true)) => (king_x + 1..8).rev().find(|x| has_piece(*x, PieceType::Rook)),
+                    Some(&(_, _, false)) => (0..king_x).find(|x| has_piece(*x, PieceType::Rook)),
+                    None if ('a'..='h').contains(&c.to_ascii_lowercase()) => {
+                        Some(c.to_ascii_lowercase() as u8 - b'a').filter(|x| has_piece(*x, PieceType::Rook))
+                    },
+                    None => None
+                }.ok_or_else(error)?;
+
+                // only one right for every side of the king
+                let right = (color, rook_x > king_x);
+
+                if rights.contains(&right) {
+                    return Err(error());
+                }
+
+                if !matches!(c, 'K' | 'Q' | 'k' | 'q') || king_x != 4 || (rook_x != 0 && rook_x != 7) {
+                    self.chess960 = true;
+                }
+
+                rights.push(right);
+                unmoved.push(Square::new(king_x, rank));
+                unmoved.push(Square::new(rook_x, rank));
+            }
+        }
+
+        for index in 0..64 {
+            let square = Square::from_index(index);
+
+            if let Some(piece) = self.pieces[index].as_mut() {
+                let moved = match piece.piece_type() {
+                    PieceType::King | PieceType::Rook => !unmoved.contains(&square),
+                    PieceType::Pawn => square.to_coords().1 != pawn_rank(piece.color()),
+                    _ => false
+                };
+
+                if moved {
+                    piece.move_piece();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_en_passant(&mut self, en_passant: &str) -> Result<(), FenError> {
+        if en_passant == "-" {
+            return Ok(());
+        }
+
+        let error = || FenError::InvalidEnPassant(en_passant.to_string());
+        let square = Square::from_uci(en_passant).map_err(|_| error())?;
+
+        let expected_rank = match self.side_to_move {
+            PieceColor::WHITE => 5,
+            PieceColor::BLACK => 2
+        };
+
+        if square.to_coords().1 != expected_rank {
+            return Err(error());
+        }
+
+        self.en_passant = Some(square);
+
+        Ok(())
+    }
+
+    /// # Returns FEN string describing the board
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    /// ```
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        for y in (0..8).rev() {
+            let mut empty = 0;
+
+            for x in 0..8 {
+                match self.get_piece(Square::new(x, y)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+
+                        fen.push(piece_to_fen(piece));
+                    },
+                    None => empty += 1
+                }
+            }
+
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+
+            if y > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen.push_str(match self.side_to_move {
+            PieceColor::WHITE => " w ",
+            PieceColor::BLACK => " b "
+        });
+
+        fen.push_str(&self.castling_field(false));
+        fen.push(' ');
+
+        match self.en_passant {
+            Some(square) => fen.push_str(&square.to_uci()),
+            None => fen.push('-')
+        }
+
+        fen.push_str(&format!(" {} {}", self.halfmove_clock, self.fullmove_number));
+        fen
+    }
+
+    /// # Returns FEN string with castling rights written as files of the rooks, Shredder-FEN
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// assert_eq!(Board::new().to_shredder_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1");
+    /// ```
+    pub fn to_shredder_fen(&self) -> String {
+        let fen = self.to_fen();
+        let fields: Vec<&str> = fen.split(' ').collect();
+
+        format!("{} {} {} {}", fields[..2].join(" "), self.castling_field(true), fields[3], fields[4..].join(" "))
+    }
+
+    /// # Returns castling rights field
+    ///
+    /// in Chess960 rights are written as X-FEN, with file letter only when another rook stands
+    /// between the castling rook and the edge of the board
+    ///
+    fn castling_field(&self, shredder: bool) -> String {
+        let castling: String = CASTLING_RIGHTS.iter()
+            .filter_map(|&(right, color, king_side)| {
+                let rook = self.castling_rook(color, king_side)?;
+                let (rook_x, rank) = rook.to_coords();
+
+                let outer_rook = if king_side { rook_x + 1..8 } else { 0..rook_x }
+                    .any(|x| self.get_piece(Square::new(x, rank)).is_some_and(|piece| piece.piece_type() == PieceType::Rook && piece.color() == color));
+
+                if shredder || (self.chess960 && outer_rook) {
+                    let file = (b'a' + rook_x) as char;
+                    Some(if color == PieceColor::WHITE { file.to_ascii_uppercase() } else { file })
+                } else {
+                    Some(right)
+                }
+            })
+            .collect();
+
+        if castling.is_empty() { "-".to_string() } else { castling }
+    }
+}
@@ -0,0 +1,256 @@
+use crate::bitboard::*;
+use crate::movement::{Move, MoveList, Square};
+use crate::piece::{PieceColor, PieceType};
+
+use super::Board;
+
+
+/// # Kind of moves returned by `Board::generate_legal` and `Board::generate_pseudo_legal`
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum MoveFilter {
+    /// every move
+    All,
+    /// moves capturing a piece, including en passant
+    Captures,
+    /// moves which are not captures, including castling and non capturing promotions
+    Quiet,
+    /// moves giving check to the opponent
+    Checks,
+    /// legal moves of side in check, there are none when side to move is not in check
+    Evasions
+}
+
+impl Board {
+    /// # Returns set of all occupied squares
+    pub(super) fn occupied(&self) -> Bitboard {
+        self.colors[PieceColor::WHITE as usize] | self.colors[PieceColor::BLACK as usize]
+    }
+
+    /// # Returns set of squares occupied by given pieces
+    pub(super) fn pieces_of(&self, color: PieceColor, piece_type: PieceType) -> Bitboard {
+        self.colors[color as usize] & self.types[piece_type as usize]
+    }
+
+    pub(super) fn king_square(&self, color: PieceColor) -> Option<Square> {
+        self.pieces_of(color, PieceType::King).first()
+    }
+
+    /// # Returns pieces of given color attacking given square
+    ///
+    /// only pieces standing on `occupied` squares are taken into account,
+    /// sliding pieces are blocked by `occupied` squares
+    ///
+    pub(super) fn attackers_to(&self, square: Square, color: PieceColor, occupied: Bitboard) -> Bitboard {
+        let queens = self.pieces_of(color, PieceType::Queen);
+
+        let attackers = (pawn_attacks(!color, square) & self.pieces_of(color, PieceType::Pawn))
+            | (knight_attacks(square) & self.pieces_of(color, PieceType::Knight))
+            | (king_attacks(square) & self.pieces_of(color, PieceType::King))
+            | (bishop_attacks(square, occupied) & (self.pieces_of(color, PieceType::Bishop) | queens))
+            | (rook_attacks(square, occupied) & (self.pieces_of(color, PieceType::Rook) | queens));
+
+        attackers & occupied
+    }
+
+    /// # Returns squares piece at given square can move to, ignoring castling and king's safety
+    pub(super) fn piece_reach(&self, square: Square) -> Bitboard {
+        let piece = match self.get_piece(square) {
+            Some(piece) => piece,
+            None => return Bitboard::EMPTY
+        };
+
+        let color = piece.color();
+        let occupied = self.occupied();
+        let own = self.colors[color as usize];
+
+        let reach = match piece.piece_type() {
+            PieceType::Pawn => {
+                let mut captures = pawn_attacks(color, square) & self.colors[!color as usize];
+
+                if let Some(target) = self.en_passant {
+                    if pawn_attacks(color, square).contains(target) && self.en_passant_capture_square(Move::new(square, target)).is_some() {
+                        captures |= Bitboard::from_square(target);
+                    }
+                }
+
+                let forward = |set: Bitboard| match color {
+                    PieceColor::WHITE => Bitboard(set.0 << 8),
+                    PieceColor::BLACK => Bitboard(set.0 >> 8)
+                } & !occupied;
+
+                let single = forward(Bitboard::from_square(square));
+                let double = if piece.moved() { Bitboard::EMPTY } else { forward(single) };
+
+                captures | single | double
+            },
+            PieceType::Knight => knight_attacks(square),
+            PieceType::Bishop => bishop_attacks(square, occupied),
+            PieceType::Rook => rook_attacks(square, occupied),
+            PieceType::Queen => queen_attacks(square, occupied),
+            PieceType::King => king_attacks(square)
+        };
+
+        reach & !own
+    }
+
+    /// # Returns true if given move leaves moving side's king attacked
+    ///
+    /// castling is not handled, see `check_castling_possibility`
+    ///
+    pub(super) fn leaves_king_attacked(&self, m: Move) -> bool {
+        let piece = self.get_piece(m.start()).unwrap();
+        let color = piece.color();
+
+        if let Some(castling) = self.castling(m) {
+            return self.check_castling_possibility(castling).is_err();
+        }
+
+        let king = if piece.piece_type() == PieceType::King {
+            m.end()
+        } else {
+            match self.king_square(color) {
+                Some(square) => square,
+                None => return false
+            }
+        };
+
+        let captured = Bitboard::from_square(self.en_passant_capture_square(m).unwrap_or(m.end()));
+        let occupied = (self.occupied() & !captured & !Bitboard::from_square(m.start())) | Bitboard::from_square(m.end());
+
+        !(self.attackers_to(king, !color, occupied) & !captured).is_empty()
+    }
+
+    /// # Returns possible castling moves of king standing at given square
+    ///
+    /// in Chess960 castling is generated as king's move to square of the rook
+    pub(super) fn castling_moves(&self, start: Square) -> impl Iterator<Item = Move> + '_ {
+        let king = self.get_piece(start).filter(|piece| piece.piece_type() == PieceType::King);
+        let y = start.to_coords().1;
+
+        [false, true].into_iter()
+            .filter_map(move |king_side| {
+                let rook = self.castling_rook(king.as_ref()?.color(), king_side)?;
+                let end = if self.chess960 { rook } else { Square::new(if king_side { 6 } else { 2 }, y) };
+
+                Some(Move::new(start, end))
+            })
+            .filter(move |m| match self.castling(*m) {
+                Some(castling) => self.check_castling_possibility(castling).is_ok(),
+                None => false
+            })
+    }
+
+    /// # Returns true if given move captures a piece, including en passant
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K2r w - d6 0 1").unwrap();
+    ///
+    /// assert!(board.is_capture("e5d6".parse().unwrap()));
+    /// assert!(board.is_capture("e1f1".parse().unwrap()) == false);
+    /// ```
+    pub fn is_capture(&self, m: Move) -> bool {
+        self.colors[!self.side_to_move as usize].contains(m.end()) || self.en_passant_capture_square(m).is_some()
+    }
+
+    /// # Returns true if given move puts opponent's king in check
+    ///
+    /// move is expected to be at least pseudo legal
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert!(board.gives_check("e2e4".parse().unwrap()) == false);
+    /// assert!(Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap().gives_check("a1a8".parse().unwrap()));
+    /// ```
+    pub fn gives_check(&self, m: Move) -> bool {
+        let color = match self.get_piece(m.start()) {
+            Some(piece) => piece.color(),
+            None => return false
+        };
+
+        let mut board = self.clone();
+        board.apply_move(m);
+        board.is_king_attacked(!color)
+    }
+
+    fn matches_filter(&self, m: Move, filter: MoveFilter) -> bool {
+        match filter {
+            MoveFilter::All => true,
+            MoveFilter::Captures => self.is_capture(m),
+            MoveFilter::Quiet => !self.is_capture(m),
+            MoveFilter::Checks => self.gives_check(m),
+            MoveFilter::Evasions => self.is_king_attacked(self.side_to_move) && !self.leaves_king_attacked(m)
+        }
+    }
+
+    /// # Returns moves of side to move which may leave own king attacked
+    ///
+    /// castling is only generated when it is legal,
+    /// pawn reaching the last rank generates one move for every promotion
+    ///
+    /// ```
+    /// # use chess_api::board::{Board, MoveFilter};
+    ///
+    /// // bishop on e2 is pinned
+    /// let board = Board::from_fen("4k3/4r3/8/8/8/8/4B3/4K3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.generate_pseudo_legal(MoveFilter::All).len(), 4 + 9);
+    /// assert_eq!(board.generate_legal(MoveFilter::All).len(), 4);
+    /// ```
+    pub fn generate_pseudo_legal(&self, filter: MoveFilter) -> MoveList {
+        let mut list = MoveList::new();
+
+        for start in self.colors[self.side_to_move as usize].squares() {
+            for end in self.piece_reach(start).squares() {
+                if self.is_promoting_move(Move::new(start, end)) {
+                    for promotion in PieceType::PROMOTIONS {
+                        list.push(Move::new_promotion(start, end, promotion));
+                    }
+                } else {
+                    list.push(Move::new(start, end));
+                }
+            }
+
+            for m in self.castling_moves(start) {
+                list.push(m);
+            }
+        }
+
+        if filter != MoveFilter::All {
+            list.retain(|m| self.matches_filter(*m, filter));
+        }
+
+        list
+    }
+
+    /// # Returns legal moves of side to move
+    ///
+    /// returned list does not borrow the board, so it can be modified while going through moves
+    ///
+    /// ```
+    /// # use chess_api::board::{Board, MoveFilter};
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// for m in board.generate_legal(MoveFilter::All) {
+    ///     let (_, undo) = board.perform_move_with_undo(m).unwrap();
+    ///     board.undo_move(undo);
+    /// }
+    ///
+    /// assert_eq!(board.generate_legal(MoveFilter::All).len(), 20);
+    /// assert!(board.generate_legal(MoveFilter::Captures).is_empty());
+    /// ```
+    pub fn generate_legal(&self, filter: MoveFilter) -> MoveList {
+        let mut list = self.generate_pseudo_legal(filter);
+
+        if filter != MoveFilter::Evasions {
+            list.retain(|m| !self.leaves_king_attacked(*m));
+        }
+
+        list
+    }
+}
@@ -0,0 +1,70 @@
+use super::{Board, MoveFilter};
+use crate::movement::Move;
+
+
+impl Board {
+    /// # Counts leaf nodes of the tree of legal moves with given depth
+    ///
+    /// used for validating move generation against known node counts
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.perft(1), 20);
+    /// assert_eq!(board.perft(2), 400);
+    /// assert_eq!(board.perft(3), 8902);
+    /// ```
+    pub fn perft(&self, depth: u32) -> u64 {
+        self.clone().perft_nodes(depth)
+    }
+
+    /// # Counts leaf nodes of perft for every legal move separately
+    ///
+    /// comparing results with another engine narrows down move with wrong node count
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::new();
+    /// let divided = board.divide(3);
+    ///
+    /// assert_eq!(divided.len(), 20);
+    /// assert_eq!(divided.iter().map(|(_, nodes)| nodes).sum::<u64>(), board.perft(3));
+    /// ```
+    pub fn divide(&self, depth: u32) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+
+        let mut board = self.clone();
+        board.generate_legal(MoveFilter::All).into_iter().map(|m| {
+            let undo = board.apply_move(m);
+            let nodes = board.perft_nodes(depth - 1);
+
+            board.undo_move(undo);
+            (m, nodes)
+        }).collect()
+    }
+
+    fn perft_nodes(&mut self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.generate_legal(MoveFilter::All);
+
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        moves.into_iter().map(|m| {
+            let undo = self.apply_move(m);
+            let nodes = self.perft_nodes(depth - 1);
+
+            self.undo_move(undo);
+            nodes
+        }).sum()
+    }
+}
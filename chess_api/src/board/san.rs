@@ -0,0 +1,224 @@
+use std::fmt;
+
+use super::{Board, PostMoveState};
+use crate::piece::*;
+use crate::movement::*;
+
+
+/// # Reason why SAN string could not be turned into a move
+#[derive(Debug, PartialEq)]
+pub enum SanError {
+    /// string is not valid SAN
+    Invalid(String),
+    /// no legal move matches given SAN
+    Illegal(String),
+    /// more than one legal move matches given SAN
+    Ambiguous(String)
+}
+
+impl fmt::Display for SanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SanError::Invalid(san) => write!(f, "'{}' is not valid SAN", san),
+            SanError::Illegal(san) => write!(f, "'{}' is not a legal move", san),
+            SanError::Ambiguous(san) => write!(f, "'{}' matches more than one legal move", san)
+        }
+    }
+}
+
+impl std::error::Error for SanError {}
+
+fn piece_type_from_san(c: char) -> Option<PieceType> {
+    match c {
+        'N' => Some(PieceType::Knight),
+        'B' => Some(PieceType::Bishop),
+        'R' => Some(PieceType::Rook),
+        'Q' => Some(PieceType::Queen),
+        'K' => Some(PieceType::King),
+        _ => None
+    }
+}
+
+fn file_from_san(c: char) -> Option<u8> {
+    match c {
+        'a'..='h' => Some(c as u8 - b'a'),
+        _ => None
+    }
+}
+
+fn rank_from_san(c: char) -> Option<u8> {
+    match c {
+        '1'..='8' => Some(c as u8 - b'1'),
+        _ => None
+    }
+}
+
+impl Board {
+    /// # Returns legal move described by SAN string
+    ///
+    /// check, mate and annotation suffixes are ignored,
+    /// castling may be written with letter O or digit 0
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::{Move, Square};
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.parse_san("Nf3").unwrap(), Move::new(Square::new(6, 0), Square::new(5, 2)));
+    /// assert_eq!(board.parse_san("e4").unwrap(), Move::new(Square::new(4, 1), Square::new(4, 3)));
+    /// assert!(board.parse_san("Ke2").is_err());
+    /// ```
+    pub fn parse_san(&self, san: &str) -> Result<Move, SanError> {
+        let trimmed = san.trim().trim_end_matches(['+', '#', '!', '?']);
+        let invalid = || SanError::Invalid(san.to_string());
+
+        let candidates: Vec<Move> = match trimmed {
+            "O-O" | "0-0" | "O-O-O" | "0-0-0" => {
+                let king_side = trimmed.len() == 3;
+
+                self.all_possible_moves(Some(self.side_to_move))
+                    .filter(|m| self.castling(*m).is_some_and(|castling| castling.king_side == king_side))
+                    .collect()
+            },
+            _ => {
+                let mut chars: Vec<char> = trimmed.chars().filter(|c| *c != 'x' && *c != '=').collect();
+
+                let promotion = match chars.last() {
+                    Some(c) if c.is_ascii_uppercase() && chars.len() > 2 => {
+                        let promotion = piece_type_from_san(*c).ok_or_else(invalid)?;
+                        chars.pop();
+                        Some(promotion)
+                    },
+                    _ => None
+                };
+
+                let piece_type = match chars.first().and_then(|c| piece_type_from_san(*c)) {
+                    Some(piece_type) => {
+                        chars.remove(0);
+                        piece_type
+                    },
+                    None => PieceType::Pawn
+                };
+
+                if chars.len() < 2 || chars.len() > 4 {
+                    return Err(invalid());
+                }
+
+                let (disambiguation, destination) = chars.split_at(chars.len() - 2);
+
+                let end = match (file_from_san(destination[0]), rank_from_san(destination[1])) {
+                    (Some(x), Some(y)) => Square::new(x, y),
+                    _ => return Err(invalid())
+                };
+
+                let (mut file, mut rank) = (None, None);
+
+                for c in disambiguation {
+                    if let (Some(x), None) = (file_from_san(*c), file) {
+                        file = Some(x);
+                    } else if let (Some(y), None) = (rank_from_san(*c), rank) {
+                        rank = Some(y);
+                    } else {
+                        return Err(invalid());
+                    }
+                }
+
+                self.all_possible_moves(Some(self.side_to_move))
+                    .filter(|m| m.end() == end && m.promotion() == promotion)
+                    .filter(|m| self.get_piece(m.start()).unwrap().piece_type() == piece_type)
+                    .filter(|m| file.is_none_or(|x| m.start().to_coords().0 == x))
+                    .filter(|m| rank.is_none_or(|y| m.start().to_coords().1 == y))
+                    .collect()
+            }
+        };
+
+        match candidates[..] {
+            [m] => Ok(m),
+            [] => Err(SanError::Illegal(san.to_string())),
+            _ => Err(SanError::Ambiguous(san.to_string()))
+        }
+    }
+
+    /// # Returns SAN string describing given legal move
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::{Move, Square};
+    ///
+    /// let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K1NR w KQkq - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.to_san(Move::new(Square::new(4, 0), Square::new(2, 0))), "O-O-O");
+    /// assert_eq!(board.to_san(Move::new(Square::new(0, 0), Square::new(0, 7))), "Rxa8+");
+    /// assert_eq!(board.to_san(Move::new(Square::new(6, 0), Square::new(5, 2))), "Nf3");
+    /// ```
+    pub fn to_san(&self, m: Move) -> String {
+        let piece = match self.get_piece(m.start()) {
+            Some(piece) => piece,
+            None => return m.to_uci()
+        };
+
+        let mut san = if let Some(castling) = self.castling(m) {
+            if castling.king_side { "O-O".to_string() } else { "O-O-O".to_string() }
+        } else {
+            let capture = self.get_piece(m.end()).is_some() || self.en_passant_capture_square(m).is_some();
+            let mut san = String::new();
+
+            if piece.piece_type() == PieceType::Pawn {
+                if capture {
+                    san.push_str(&m.start().to_uci()[..1]);
+                }
+            } else {
+                san.push(piece.piece_type().to_uci().to_ascii_uppercase());
+                san.push_str(&self.san_disambiguation(m, piece.piece_type()));
+            }
+
+            if capture {
+                san.push('x');
+            }
+
+            san.push_str(&m.end().to_uci());
+
+            if let Some(promotion) = m.promotion() {
+                san.push('=');
+                san.push(promotion.to_uci().to_ascii_uppercase());
+            }
+
+            san
+        };
+
+        let mut board = self.clone();
+
+        if let Ok(state) = board.perform_move(m) {
+            if state == PostMoveState::Checkmate {
+                san.push('#');
+            } else if board.is_king_attacked(board.side_to_move) {
+                san.push('+');
+            }
+        }
+
+        san
+    }
+
+    /// # Returns shortest start square part distinguishing move from other moves of same piece type
+    fn san_disambiguation(&self, m: Move, piece_type: PieceType) -> String {
+        let (sx, sy) = m.start().to_coords();
+        let color = self.get_piece(m.start()).unwrap().color();
+
+        let others: Vec<Square> = self.all_possible_moves(Some(color))
+            .filter(|other| other.end() == m.end() && other.start() != m.start())
+            .filter(|other| self.get_piece(other.start()).unwrap().piece_type() == piece_type)
+            .map(|other| other.start())
+            .collect();
+
+        let start = m.start().to_uci();
+
+        if others.is_empty() {
+            String::new()
+        } else if others.iter().all(|other| other.to_coords().0 != sx) {
+            start[..1].to_string()
+        } else if others.iter().all(|other| other.to_coords().1 != sy) {
+            start[1..].to_string()
+        } else { start }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::bitboard::Bitboard;
+use crate::movement::Move;
+use crate::piece::{PieceType, PieceValues};
+
+use super::Board;
+
+
+/// longest possible sequence of captures on one square
+const MAX_EXCHANGES: usize = 32;
+
+impl Board {
+    /// # Returns material balance of the exchange started by given move, using default piece values
+    ///
+    /// see `see_with_values`
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// // knight on e5 is defended by pawn on d6
+    /// let board = Board::from_fen("4k3/8/3p4/4n3/3P4/8/8/4K3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.see("d4e5".parse().unwrap()), 320 - 100);
+    /// ```
+    pub fn see(&self, m: Move) -> i32 {
+        self.see_with_values(m, &PieceValues::default())
+    }
+
+    /// # Returns material balance of the exchange started by given move
+    ///
+    /// both sides keep capturing on move's destination with their least valuable attacker,
+    /// each side can stop capturing when continuing would lose material,
+    /// sliding pieces standing behind other attackers join the exchange once the line is open
+    ///
+    /// pins are not taken into account, king only captures when square is no longer defended,
+    /// quiet moves can be evaluated too, resulting value is then zero or negative
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::PieceValues;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/3p4/8/8/8/4QK2 w - - 0 1").unwrap();
+    /// let values = PieceValues { queen: 1000, ..PieceValues::default() };
+    ///
+    /// assert_eq!(board.see_with_values("e1e4".parse().unwrap(), &values), -1000); // queen moves next to pawn
+    /// ```
+    pub fn see_with_values(&self, m: Move, values: &PieceValues) -> i32 {
+        let (from, to) = m.to_squares();
+
+        let piece = match self.get_piece(from) {
+            Some(piece) => piece,
+            None => return 0
+        };
+
+        // king moving onto own rook in Chess960 captures nothing
+        if self.castling(m).is_some() {
+            return 0;
+        }
+
+        let promotion_rank = Bitboard(0xff000000000000ff);
+        let promotion_gain = |piece_type: PieceType| values.value(piece_type) - values.pawn;
+
+        let mut occupied = self.occupied() & !Bitboard::from_square(from);
+        let mut gain = [0; MAX_EXCHANGES];
+
+        gain[0] = match self.en_passant_capture_square(m) {
+            Some(captured) => {
+                occupied &= !Bitboard::from_square(captured);
+                values.pawn
+            },
+            None => self.get_piece(to).map_or(0, |captured| values.value(captured.piece_type()))
+        };
+
+        let mut on_square = match m.promotion() {
+            Some(promotion) => {
+                gain[0] += promotion_gain(promotion);
+                values.value(promotion)
+            },
+            None => values.value(piece.piece_type())
+        };
+
+        let mut color = !piece.color();
+        let mut depth = 0;
+
+        while depth + 1 < MAX_EXCHANGES {
+            let attackers = self.attackers_to(to, color, occupied);
+
+            let attacker = [PieceType::Pawn, PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen, PieceType::King]
+                .into_iter()
+                .find_map(|piece_type| (attackers & self.types[piece_type as usize]).first().map(|square| (square, piece_type)));
+
+            let (square, piece_type) = match attacker {
+                Some(attacker) => attacker,
+                None => break
+            };
+
+            occupied &= !Bitboard::from_square(square);
+
+            if piece_type == PieceType::King && !self.attackers_to(to, !color, occupied).is_empty() {
+                break;
+            }
+
+            depth += 1;
+            gain[depth] = on_square - gain[depth - 1];
+            on_square = values.value(piece_type);
+
+            if piece_type == PieceType::Pawn && promotion_rank.contains(to) {
+                gain[depth] += promotion_gain(PieceType::Queen);
+                on_square = values.queen;
+            }
+
+            color = !color;
+        }
+
+        while depth > 0 {
+            gain[depth - 1] = -i32::max(-gain[depth - 1], gain[depth]);
+            depth -= 1;
+        }
+
+        gain[0]
+    }
+}
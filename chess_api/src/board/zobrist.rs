@@ -0,0 +1,145 @@
+use super::Board;
+use crate::piece::*;
+use crate::movement::*;
+
+
+struct ZobristKeys {
+    pieces: [[u64; 64]; 12],
+    side: u64,
+    castling: [u64; 4],
+    en_passant: [u64; 8]
+}
+
+/// # SplitMix64 step, returns new state and generated number
+const fn split_mix(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = state;
+
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+    (state, z ^ (z >> 31))
+}
+
+/// # Generates keys at compile time, so hashes stay the same between runs and builds
+const fn generate_keys() -> ZobristKeys {
+    let mut keys = ZobristKeys {
+        pieces: [[0; 64]; 12],
+        side: 0,
+        castling: [0; 4],
+        en_passant: [0; 8]
+    };
+
+    let mut state = 0x5445_524D_4348_4553;
+    let mut key;
+
+    let mut i = 0;
+    while i < 12 {
+        let mut j = 0;
+        while j < 64 {
+            (state, key) = split_mix(state);
+            keys.pieces[i][j] = key;
+            j += 1;
+        }
+        i += 1;
+    }
+
+    (state, key) = split_mix(state);
+    keys.side = key;
+
+    let mut i = 0;
+    while i < 4 {
+        (state, key) = split_mix(state);
+        keys.castling[i] = key;
+        i += 1;
+    }
+
+    let mut i = 0;
+    while i < 8 {
+        (state, key) = split_mix(state);
+        keys.en_passant[i] = key;
+        i += 1;
+    }
+
+    keys
+}
+
+static KEYS: ZobristKeys = generate_keys();
+
+/// # Returns key of piece standing on given square
+pub(super) fn piece_key(square: Square, piece: &Piece) -> u64 {
+    let color = match piece.color() {
+        PieceColor::WHITE => 0,
+        PieceColor::BLACK => 6
+    };
+
+    KEYS.pieces[color + piece.piece_type() as usize][square.to_index()]
+}
+
+impl Board {
+    /// # Returns Zobrist hash of the position
+    ///
+    /// covers piece placement, side to move, castling rights and en passant file,
+    /// en passant file is only included when pawn of side to move stands next to the pawn which can be captured
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let mut a = Board::new();
+    /// let mut b = Board::new();
+    ///
+    /// for san in ["Nf3", "Nf6", "Nc3"] {
+    ///     assert!(a.perform_move(a.parse_san(san).unwrap()).is_ok());
+    /// }
+    ///
+    /// for san in ["Nc3", "Nf6", "Nf3"] {
+    ///     assert!(b.perform_move(b.parse_san(san).unwrap()).is_ok());
+    /// }
+    ///
+    /// assert_eq!(a.hash(), b.hash());
+    /// assert_ne!(a.hash(), Board::new().hash());
+    /// ```
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// # Computes hash of the position from scratch
+    pub(super) fn compute_hash(&self) -> u64 {
+        self.pieces(None).fold(self.state_hash(), |hash, (square, piece)| hash ^ piece_key(square, piece))
+    }
+
+    /// # Returns part of the hash not related to piece placement
+    pub(super) fn state_hash(&self) -> u64 {
+        let mut hash = 0;
+
+        if self.side_to_move == PieceColor::BLACK {
+            hash ^= KEYS.side;
+        }
+
+        let rights = [(PieceColor::WHITE, true), (PieceColor::WHITE, false), (PieceColor::BLACK, true), (PieceColor::BLACK, false)];
+
+        for (i, (color, king_side)) in rights.into_iter().enumerate() {
+            if self.has_castling_right(color, king_side) {
+                hash ^= KEYS.castling[i];
+            }
+        }
+
+        if let Some(square) = self.en_passant {
+            let (x, y) = square.to_coords();
+            let pawn_rank = match self.side_to_move {
+                PieceColor::WHITE => y - 1,
+                PieceColor::BLACK => y + 1
+            };
+
+            let can_capture = [x.checked_sub(1), Some(x + 1).filter(|x| *x < 8)].into_iter().flatten().any(|x| {
+                self.get_piece(Square::new(x, pawn_rank)).is_some_and(|piece| piece.piece_type() == PieceType::Pawn && piece.color() == self.side_to_move)
+            });
+
+            if can_capture {
+                hash ^= KEYS.en_passant[x as usize];
+            }
+        }
+
+        hash
+    }
+}
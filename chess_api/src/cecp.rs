@@ -0,0 +1,326 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::board::{Board, MoveFilter, SanError};
+use crate::engine::BestMove;
+use crate::movement::Move;
+use crate::uci::CommandError;
+
+
+/// score of mate in one move in thinking output, mate in n moves is scored `MATE_SCORE + n`
+pub const MATE_SCORE: i32 = 100000;
+
+/// # Command sent from interface to engine in Chess Engine Communication Protocol
+///
+/// clocks are sent in centiseconds, `level` and `st` in minutes and seconds
+///
+/// ```
+/// use std::time::Duration;
+/// use chess_api::cecp::Command;
+///
+/// assert_eq!("time 3000".parse(), Ok(Command::Time(Duration::from_secs(30))));
+/// assert_eq!("usermove e2e4".parse(), Ok(Command::UserMove("e2e4".to_string())));
+/// assert_eq!("level 40 2:30 0".parse(), Ok(Command::Level {
+///     moves: 40,
+///     base: Duration::from_secs(150),
+///     increment: Duration::ZERO
+/// }));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub enum Command {
+    Xboard,
+    Protover(u32),
+    Accepted(String),
+    Rejected(String),
+    New,
+    Variant(String),
+    Force,
+    Go,
+    PlayOther,
+    /// move in coordinate notation or SAN, checked against the board when played
+    UserMove(String),
+    /// `?`, engine should play immediately
+    MoveNow,
+    /// engine's remaining time
+    Time(Duration),
+    /// opponent's remaining time
+    Otim(Duration),
+    /// `moves` moves have to be played in `base` time, 0 for whole game, `increment` is added after every move
+    Level { moves: u32, base: Duration, increment: Duration },
+    /// fixed time per move
+    St(Duration),
+    /// depth limit
+    Sd(u32),
+    SetBoard(String),
+    Undo,
+    Remove,
+    Result { result: String, comment: Option<String> },
+    Post,
+    NoPost,
+    Hard,
+    Easy,
+    Ping(u32),
+    Draw,
+    Quit
+}
+
+impl FromStr for Command {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (command, rest) = s.split_once(' ').map(|(command, rest)| (command, rest.trim())).unwrap_or((s, ""));
+        let args: Vec<&str> = rest.split_whitespace().collect();
+
+        let value = |i: usize| args.get(i).copied().ok_or_else(|| CommandError::MissingValue(command.to_string()));
+        let number = |i: usize| value(i).and_then(|arg| arg.parse::<u32>().map_err(|_| CommandError::InvalidValue(arg.to_string())));
+        let centiseconds = |i: usize| value(i).and_then(|arg| arg.parse::<i64>().map_err(|_| CommandError::InvalidValue(arg.to_string())))
+            .map(|cs| Duration::from_millis(cs.max(0) as u64 * 10));
+
+        match command {
+            "" => Err(CommandError::Empty),
+            "xboard" => Ok(Command::Xboard),
+            "protover" => number(0).map(Command::Protover),
+            "accepted" => value(0).map(|feature| Command::Accepted(feature.to_string())),
+            "rejected" => value(0).map(|feature| Command::Rejected(feature.to_string())),
+            "new" => Ok(Command::New),
+            "variant" => value(0).map(|variant| Command::Variant(variant.to_string())),
+            "force" => Ok(Command::Force),
+            "go" => Ok(Command::Go),
+            "playother" => Ok(Command::PlayOther),
+            "usermove" => value(0).map(|m| Command::UserMove(m.to_string())),
+            "?" => Ok(Command::MoveNow),
+            "time" => centiseconds(0).map(Command::Time),
+            "otim" => centiseconds(0).map(Command::Otim),
+            "level" => Ok(Command::Level {
+                moves: number(0)?,
+                base: parse_minutes(value(1)?)?,
+                increment: parse_seconds(value(2)?)?
+            }),
+            "st" => parse_seconds(value(0)?).map(Command::St),
+            "sd" => number(0).map(Command::Sd),
+            "setboard" if !rest.is_empty() => Ok(Command::SetBoard(rest.to_string())),
+            "setboard" => Err(CommandError::MissingValue(command.to_string())),
+            "undo" => Ok(Command::Undo),
+            "remove" => Ok(Command::Remove),
+            "result" => {
+                let (result, comment) = split_result(rest);
+                value(0).map(|_| Command::Result { result, comment })
+            },
+            "post" => Ok(Command::Post),
+            "nopost" => Ok(Command::NoPost),
+            "hard" => Ok(Command::Hard),
+            "easy" => Ok(Command::Easy),
+            "ping" => number(0).map(Command::Ping),
+            "draw" => Ok(Command::Draw),
+            "quit" => Ok(Command::Quit),
+            _ => Err(CommandError::UnknownCommand(command.to_string()))
+        }
+    }
+}
+
+/// # Parses minutes of `level` command, seconds can follow after colon
+fn parse_minutes(s: &str) -> Result<Duration, CommandError> {
+    let invalid = || CommandError::InvalidValue(s.to_string());
+    let (minutes, seconds) = s.split_once(':').unwrap_or((s, "0"));
+
+    let minutes: u64 = minutes.parse().map_err(|_| invalid())?;
+    let seconds: u64 = seconds.parse().map_err(|_| invalid())?;
+
+    Ok(Duration::from_secs(minutes * 60 + seconds))
+}
+
+/// # Parses seconds, which can have fractional part
+fn parse_seconds(s: &str) -> Result<Duration, CommandError> {
+    match s.parse::<f64>() {
+        Ok(seconds) if seconds >= 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(CommandError::InvalidValue(s.to_string()))
+    }
+}
+
+/// # Splits `1-0 {White mates}` into result and comment without braces
+fn split_result(s: &str) -> (String, Option<String>) {
+    match s.split_once(' ') {
+        Some((result, comment)) => {
+            let comment = comment.trim().trim_start_matches('{').trim_end_matches('}').trim();
+            (result.to_string(), Some(comment.to_string()).filter(|comment| !comment.is_empty()))
+        },
+        None => (s.to_string(), None)
+    }
+}
+
+/// # Returns legal move written in coordinate notation or SAN
+///
+/// ```
+/// # use chess_api::board::Board;
+/// # use chess_api::cecp::parse_move;
+///
+/// let board = Board::new();
+///
+/// assert_eq!(parse_move(&board, "g1f3"), parse_move(&board, "Nf3"));
+/// assert!(parse_move(&board, "e2e5").is_err());
+/// ```
+pub fn parse_move(board: &Board, s: &str) -> Result<Move, SanError> {
+    match s.parse::<Move>() {
+        Ok(m) if board.generate_legal(MoveFilter::All).contains(&m) => Ok(m),
+        Ok(_) => Err(SanError::Illegal(s.to_string())),
+        Err(_) => board.parse_san(s)
+    }
+}
+
+/// # One line of thinking output sent by engine after `post`
+///
+/// ```
+/// use std::time::Duration;
+/// use chess_api::cecp::Thinking;
+///
+/// let thinking: Thinking = "9 156 1084 48000 Nf3 Nc6 Nc3".parse().unwrap();
+///
+/// assert_eq!(thinking.depth, 9);
+/// assert_eq!(thinking.score, 156);
+/// assert_eq!(thinking.time, Duration::from_millis(10840));
+/// assert_eq!(thinking.pv, "Nf3 Nc6 Nc3");
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Thinking {
+    pub depth: u32,
+    /// centipawns from engine's point of view, mates are scored around `MATE_SCORE`
+    pub score: i32,
+    pub time: Duration,
+    pub nodes: u64,
+    pub pv: String
+}
+
+impl Thinking {
+    /// # Creates thinking output of search result in given position, principal variation is written in SAN
+    pub fn new(best: &BestMove, board: &Board) -> Thinking {
+        let mut board = board.clone();
+
+        let pv: Vec<String> = best.pv().iter().map(|m| {
+            let san = board.to_san(*m);
+            board.apply_move(*m);
+            san
+        }).collect();
+
+        let score = match best.mate_in() {
+            Some(moves) if moves > 0 => MATE_SCORE + moves,
+            Some(moves) => -MATE_SCORE + moves,
+            None => best.score()
+        };
+
+        Thinking {
+            depth: best.depth(),
+            score,
+            time: best.time(),
+            nodes: best.nodes(),
+            pv: pv.join(" ")
+        }
+    }
+}
+
+impl fmt::Display for Thinking {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} {} {}", self.depth, self.score, self.time.as_millis() / 10, self.nodes, self.pv)
+    }
+}
+
+impl FromStr for Thinking {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.trim().splitn(5, char::is_whitespace);
+
+        let mut next = |name: &str| match fields.next() {
+            Some(field) if !field.is_empty() => Ok(field),
+            _ => Err(CommandError::MissingValue(name.to_string()))
+        };
+
+        fn number<T: FromStr>(field: &str) -> Result<T, CommandError> {
+            field.parse().map_err(|_| CommandError::InvalidValue(field.to_string()))
+        }
+
+        Ok(Thinking {
+            // some engines mark depth with selective search, e.g. `12&`
+            depth: number(next("depth")?.trim_end_matches(|c: char| !c.is_ascii_digit()))?,
+            score: number(next("score")?)?,
+            time: Duration::from_millis(number::<u64>(next("time")?)? * 10),
+            nodes: number(next("nodes")?)?,
+            pv: next("pv").map(|pv| pv.trim().to_string()).unwrap_or_default()
+        })
+    }
+}
+
+/// # Message sent from engine to interface
+///
+/// ```
+/// use chess_api::cecp::EngineMessage;
+///
+/// assert_eq!("move e7e5".parse(), Ok(EngineMessage::Move("e7e5".to_string())));
+/// assert_eq!("feature usermove=1 myname=\"Some Engine\"".parse(), Ok(EngineMessage::Feature(vec![
+///     ("usermove".to_string(), "1".to_string()),
+///     ("myname".to_string(), "Some Engine".to_string())
+/// ])));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub enum EngineMessage {
+    /// names and values of features, quotes are removed
+    Feature(Vec<(String, String)>),
+    Move(String),
+    Thinking(Thinking),
+    Result { result: String, comment: Option<String> },
+    Resign,
+    OfferDraw,
+    Pong(u32),
+    /// `Illegal move: <move>` or `Illegal move (<reason>): <move>`
+    IllegalMove(String),
+    /// `Error (<type>): <command>`
+    Error(String)
+}
+
+impl FromStr for EngineMessage {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (message, rest) = s.split_once(' ').map(|(message, rest)| (message, rest.trim())).unwrap_or((s, ""));
+
+        let after_colon = || s.split_once(':').map(|(_, m)| m.trim().to_string()).unwrap_or_default();
+
+        match message {
+            "" => Err(CommandError::Empty),
+            "feature" => Ok(EngineMessage::Feature(parse_features(rest))),
+            "move" if !rest.is_empty() => Ok(EngineMessage::Move(rest.to_string())),
+            "1-0" | "0-1" | "1/2-1/2" | "*" => {
+                let (result, comment) = split_result(s);
+                Ok(EngineMessage::Result { result, comment })
+            },
+            "resign" => Ok(EngineMessage::Resign),
+            "offer" if rest == "draw" => Ok(EngineMessage::OfferDraw),
+            "pong" => rest.parse().map(EngineMessage::Pong).map_err(|_| CommandError::InvalidValue(rest.to_string())),
+            "Illegal" => Ok(EngineMessage::IllegalMove(after_colon())),
+            "Error" => Ok(EngineMessage::Error(after_colon())),
+            // thinking lines start with depth
+            _ if message.starts_with(|c: char| c.is_ascii_digit()) => s.parse().map(EngineMessage::Thinking),
+            _ => Err(CommandError::UnknownCommand(message.to_string()))
+        }
+    }
+}
+
+/// # Parses `name=value` pairs, values can be quoted and contain spaces
+fn parse_features(s: &str) -> Vec<(String, String)> {
+    let mut features = Vec::new();
+    let mut rest = s.trim();
+
+    while let Some((name, after)) = rest.split_once('=') {
+        let (value, after) = match after.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+            None => after.split_once(' ').unwrap_or((after, ""))
+        };
+
+        features.push((name.trim().to_string(), value.to_string()));
+        rest = after.trim();
+    }
+
+    features
+}
@@ -0,0 +1,300 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::board::Board;
+use crate::game::Game;
+use crate::movement::Move;
+
+mod eval;
+mod search;
+mod strength;
+mod tt;
+
+pub use eval::evaluate;
+pub use strength::Strength;
+
+use search::Searcher;
+use tt::TranspositionTable;
+
+
+/// score of position where side to move is checkmated, decreased by distance from root
+pub const MATE: i32 = 30000;
+
+/// deepest search iteration, also used when no depth limit is given
+pub const MAX_DEPTH: u32 = 64;
+
+/// default size of transposition table in megabytes
+const DEFAULT_HASH_SIZE: usize = 16;
+
+/// number of moves remaining time is split into when it is not known
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+
+/// time kept on the clock for communication delays
+const MOVE_OVERHEAD: Duration = Duration::from_millis(50);
+
+/// # Conditions ending the search
+///
+/// search ends when any of given limits is reached,
+/// move found in the deepest fully searched iteration is returned
+///
+/// ```
+/// use std::time::Duration;
+/// use chess_api::engine::SearchLimits;
+///
+/// let limits = SearchLimits { depth: Some(6), time: Some(Duration::from_millis(500)), ..SearchLimits::default() };
+///
+/// assert_eq!(limits.nodes, None);
+/// assert_eq!(SearchLimits::depth(4).depth, Some(4));
+/// ```
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub struct SearchLimits {
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+    pub time: Option<Duration>
+}
+
+impl SearchLimits {
+    pub fn depth(depth: u32) -> SearchLimits {
+        SearchLimits { depth: Some(depth), ..SearchLimits::default() }
+    }
+
+    pub fn nodes(nodes: u64) -> SearchLimits {
+        SearchLimits { nodes: Some(nodes), ..SearchLimits::default() }
+    }
+
+    pub fn time(time: Duration) -> SearchLimits {
+        SearchLimits { time: Some(time), ..SearchLimits::default() }
+    }
+
+    /// # Returns time limit for one move of a game played with clock
+    ///
+    /// remaining time is split evenly between `moves_to_go` moves, or 30 moves when unknown,
+    /// most of the increment is used on top of that
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use chess_api::engine::SearchLimits;
+    ///
+    /// let limits = SearchLimits::from_clock(Duration::from_secs(60), Duration::ZERO, Some(10));
+    /// assert_eq!(limits.time, Some(Duration::from_secs(6)));
+    ///
+    /// // never more than what is left on the clock
+    /// let limits = SearchLimits::from_clock(Duration::from_millis(200), Duration::from_secs(2), None);
+    /// assert!(limits.time.unwrap() < Duration::from_millis(200));
+    /// ```
+    pub fn from_clock(remaining: Duration, increment: Duration, moves_to_go: Option<u32>) -> SearchLimits {
+        let moves = moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).clamp(1, DEFAULT_MOVES_TO_GO);
+        let time = (remaining / moves + increment * 3 / 4)
+            .min(remaining.saturating_sub(MOVE_OVERHEAD))
+            .max(Duration::from_millis(1));
+
+        SearchLimits::time(time)
+    }
+}
+
+/// # Result of the search
+#[derive(Debug, Clone)]
+pub struct BestMove {
+    m: Option<Move>,
+    score: i32,
+    depth: u32,
+    nodes: u64,
+    time: Duration,
+    pv: Vec<Move>
+}
+
+impl BestMove {
+    /// # Returns best move found, None when side to move has no legal moves
+    pub fn get_move(&self) -> Option<Move> {
+        self.m
+    }
+
+    /// # Returns score in centipawns from side to move's point of view
+    ///
+    /// mate is scored as `MATE` decreased by number of halfmoves needed to give it
+    ///
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+
+    /// # Returns number of moves to mate, negative when side to move gets mated
+    pub fn mate_in(&self) -> Option<i32> {
+        if self.score > MATE - MAX_DEPTH as i32 * 2 {
+            Some((MATE - self.score + 1) / 2)
+        } else if self.score < -MATE + MAX_DEPTH as i32 * 2 {
+            Some(-(MATE + self.score) / 2)
+        } else { None }
+    }
+
+    /// # Returns depth of the deepest fully searched iteration
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// # Returns number of searched positions
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// # Returns time spent searching
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    /// # Returns expected continuation, starting with the best move
+    pub fn pv(&self) -> &[Move] {
+        &self.pv
+    }
+}
+
+/// # Chess engine keeping its transposition table between searches
+///
+/// ```
+/// # use chess_api::board::Board;
+/// # use chess_api::engine::{Engine, SearchLimits};
+///
+/// let mut engine = Engine::new();
+///
+/// // white mates with Qd8
+/// let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/3Q2K1 w - - 0 1").unwrap();
+/// let best = engine.search(&board, SearchLimits::depth(3));
+///
+/// assert_eq!(best.get_move().unwrap().to_uci(), "d1d8");
+/// assert_eq!(best.mate_in(), Some(1));
+/// ```
+pub struct Engine {
+    tt: TranspositionTable,
+    strength: Strength,
+    seed: u64,
+    stop: Arc<AtomicBool>
+}
+
+impl Engine {
+    pub fn new() -> Engine {
+        Engine::with_hash_size(DEFAULT_HASH_SIZE)
+    }
+
+    /// # Creates engine with transposition table taking about given number of megabytes
+    pub fn with_hash_size(megabytes: usize) -> Engine {
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_nanos() as u64).unwrap_or(0);
+
+        Engine {
+            tt: TranspositionTable::new(megabytes),
+            strength: Strength::full(),
+            seed,
+            stop: Arc::new(AtomicBool::new(false))
+        }
+    }
+
+    /// # Weakens the engine, see `Strength::level`
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::engine::{Engine, SearchLimits, Strength};
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.set_strength(Strength::level(1));
+    ///
+    /// let best = engine.search(&Board::new(), SearchLimits::default());
+    /// assert_eq!(best.depth(), 1);
+    /// ```
+    pub fn set_strength(&mut self, strength: Strength) {
+        self.strength = strength;
+    }
+
+    pub fn strength(&self) -> Strength {
+        self.strength
+    }
+
+    /// # Sets seed of random choices made by weakened engine, making them repeatable
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// # Returns flag which ends running search when set
+    ///
+    /// search started while the flag is set ends immediately,
+    /// so it has to be cleared before next search
+    ///
+    /// ```
+    /// # use std::sync::atomic::Ordering;
+    /// # use chess_api::board::Board;
+    /// # use chess_api::engine::{Engine, SearchLimits};
+    ///
+    /// let mut engine = Engine::new();
+    /// let stop = engine.stop_flag();
+    ///
+    /// stop.store(true, Ordering::Relaxed);
+    /// let best = engine.search(&Board::new(), SearchLimits::default());
+    ///
+    /// assert_eq!(best.depth(), 0);
+    /// assert!(best.get_move().is_some());
+    /// ```
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.stop)
+    }
+
+    /// # Searches for the best move in given position
+    ///
+    /// previous positions are unknown, so repetitions are only detected within the search
+    ///
+    pub fn search(&mut self, board: &Board, limits: SearchLimits) -> BestMove {
+        self.run_search(board, &[], limits, &mut |_| ())
+    }
+
+    /// # Searches for the best move in current position of given game, avoiding or seeking repetitions
+    pub fn search_game(&mut self, game: &Game, limits: SearchLimits) -> BestMove {
+        self.search_game_with_info(game, limits, |_| ())
+    }
+
+    /// # Searches like `search_game`, calling `info` after every finished iteration
+    ///
+    /// ```
+    /// # use chess_api::game::Game;
+    /// # use chess_api::engine::{Engine, SearchLimits};
+    ///
+    /// let mut depths = Vec::new();
+    /// let best = Engine::new().search_game_with_info(&Game::new(), SearchLimits::depth(3), |info| depths.push(info.depth()));
+    ///
+    /// assert_eq!(depths, [1, 2, 3]);
+    /// assert_eq!(best.depth(), 3);
+    /// ```
+    pub fn search_game_with_info<F: FnMut(&BestMove)>(&mut self, game: &Game, limits: SearchLimits, mut info: F) -> BestMove {
+        self.run_search(game.board(), game.position_hashes(), limits, &mut info)
+    }
+
+    fn run_search(&mut self, board: &Board, positions: &[u64], limits: SearchLimits, info: &mut dyn FnMut(&BestMove)) -> BestMove {
+        let seed = strength::next_random(&mut self.seed);
+
+        Searcher::new(board, positions, self.strength.restrict(limits), &self.strength, seed, &mut self.tt, &self.stop).run(info)
+    }
+
+    /// # Forgets all searched positions, should be called before new game
+    pub fn clear(&mut self) {
+        self.tt.clear();
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Engine::new()
+    }
+}
+
+/// # Searches for the best move in given position with new engine
+///
+/// ```
+/// # use chess_api::board::Board;
+/// # use chess_api::engine::{search, SearchLimits};
+///
+/// // black's queen on d4 is unprotected
+/// let board = Board::from_fen("4k3/8/8/8/3q4/8/8/3RK3 w - - 0 1").unwrap();
+/// let best = search(&board, SearchLimits::depth(2));
+///
+/// assert_eq!(best.get_move().unwrap().to_uci(), "d1d4");
+/// assert!(best.score() > 400);
+/// ```
+pub fn search(board: &Board, limits: SearchLimits) -> BestMove {
+    Engine::with_hash_size(1).search(board, limits)
+}
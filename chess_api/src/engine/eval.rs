@@ -0,0 +1,165 @@
+use crate::board::Board;
+use crate::movement::Square;
+use crate::piece::{PieceColor, PieceType, PieceValues};
+
+
+// piece-square tables from white's point of view, first row is the 8th rank
+#[rustfmt::skip]
+const PAWN_TABLE: [i32; 64] = [
+      0,   0,   0,   0,   0,   0,   0,   0,
+     50,  50,  50,  50,  50,  50,  50,  50,
+     10,  10,  20,  30,  30,  20,  10,  10,
+      5,   5,  10,  25,  25,  10,   5,   5,
+      0,   0,   0,  20,  20,   0,   0,   0,
+      5,  -5, -10,   0,   0, -10,  -5,   5,
+      5,  10,  10, -20, -20,  10,  10,   5,
+      0,   0,   0,   0,   0,   0,   0,   0
+];
+
+#[rustfmt::skip]
+const KNIGHT_TABLE: [i32; 64] = [
+    -50, -40, -30, -30, -30, -30, -40, -50,
+    -40, -20,   0,   0,   0,   0, -20, -40,
+    -30,   0,  10,  15,  15,  10,   0, -30,
+    -30,   5,  15,  20,  20,  15,   5, -30,
+    -30,   0,  15,  20,  20,  15,   0, -30,
+    -30,   5,  10,  15,  15,  10,   5, -30,
+    -40, -20,   0,   5,   5,   0, -20, -40,
+    -50, -40, -30, -30, -30, -30, -40, -50
+];
+
+#[rustfmt::skip]
+const BISHOP_TABLE: [i32; 64] = [
+    -20, -10, -10, -10, -10, -10, -10, -20,
+    -10,   0,   0,   0,   0,   0,   0, -10,
+    -10,   0,   5,  10,  10,   5,   0, -10,
+    -10,   5,   5,  10,  10,   5,   5, -10,
+    -10,   0,  10,  10,  10,  10,   0, -10,
+    -10,  10,  10,  10,  10,  10,  10, -10,
+    -10,   5,   0,   0,   0,   0,   5, -10,
+    -20, -10, -10, -10, -10, -10, -10, -20
+];
+
+#[rustfmt::skip]
+const ROOK_TABLE: [i32; 64] = [
+      0,   0,   0,   0,   0,   0,   0,   0,
+      5,  10,  10,  10,  10,  10,  10,   5,
+     -5,   0,   0,   0,   0,   0,   0,  -5,
+     -5,   0,   0,   0,   0,   0,   0,  -5,
+     -5,   0,   0,   0,   0,   0,   0,  -5,
+     -5,   0,   0,   0,   0,   0,   0,  -5,
+     -5,   0,   0,   0,   0,   0,   0,  -5,
+      0,   0,   0,   5,   5,   0,   0,   0
+];
+
+#[rustfmt::skip]
+const QUEEN_TABLE: [i32; 64] = [
+    -20, -10, -10,  -5,  -5, -10, -10, -20,
+    -10,   0,   0,   0,   0,   0,   0, -10,
+    -10,   0,   5,   5,   5,   5,   0, -10,
+     -5,   0,   5,   5,   5,   5,   0,  -5,
+      0,   0,   5,   5,   5,   5,   0,  -5,
+    -10,   5,   5,   5,   5,   5,   0, -10,
+    -10,   0,   5,   0,   0,   0,   0, -10,
+    -20, -10, -10,  -5,  -5, -10, -10, -20
+];
+
+#[rustfmt::skip]
+const KING_MIDDLEGAME_TABLE: [i32; 64] = [
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -20, -30, -30, -40, -40, -30, -30, -20,
+    -10, -20, -20, -20, -20, -20, -20, -10,
+     20,  20,   0,   0,   0,   0,  20,  20,
+     20,  30,  10,   0,   0,  10,  30,  20
+];
+
+#[rustfmt::skip]
+const KING_ENDGAME_TABLE: [i32; 64] = [
+    -50, -40, -30, -20, -20, -30, -40, -50,
+    -30, -20, -10,   0,   0, -10, -20, -30,
+    -30, -10,  20,  30,  30,  20, -10, -30,
+    -30, -10,  30,  40,  40,  30, -10, -30,
+    -30, -10,  30,  40,  40,  30, -10, -30,
+    -30, -10,  20,  30,  30,  20, -10, -30,
+    -30, -30,   0,   0,   0,   0, -30, -30,
+    -50, -30, -30, -30, -30, -30, -30, -50
+];
+
+/// game phase when all pieces but pawns and kings are on the board
+const MAX_PHASE: i32 = 24;
+
+fn phase_weight(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Knight | PieceType::Bishop => 1,
+        PieceType::Rook => 2,
+        PieceType::Queen => 4,
+        PieceType::Pawn | PieceType::King => 0
+    }
+}
+
+/// # Returns index into piece-square table for piece of given color
+fn table_index(square: Square, color: PieceColor) -> usize {
+    let (x, y) = square.to_coords();
+
+    match color {
+        PieceColor::WHITE => (7 - y as usize) * 8 + x as usize,
+        PieceColor::BLACK => y as usize * 8 + x as usize
+    }
+}
+
+/// # Returns static evaluation of the position in centipawns
+///
+/// sum of material and piece-square bonuses, positive when side to move is better,
+/// king's table is interpolated between middlegame and endgame by remaining material
+///
+/// ```
+/// # use chess_api::board::Board;
+/// # use chess_api::engine::evaluate;
+///
+/// assert_eq!(evaluate(&Board::new()), 0);
+///
+/// // white is missing a queen
+/// let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNB1KBNR w KQkq - 0 1").unwrap();
+/// assert!(evaluate(&board) < -800);
+/// ```
+pub fn evaluate(board: &Board) -> i32 {
+    let values = PieceValues::default();
+
+    let mut score = 0;
+    let mut phase = 0;
+    let mut kings = [None, None];
+
+    for (square, piece) in board.pieces(None) {
+        let color = piece.color();
+        let index = table_index(square, color);
+        let sign = if color == board.side_to_move() { 1 } else { -1 };
+
+        phase += phase_weight(piece.piece_type());
+
+        let bonus = match piece.piece_type() {
+            PieceType::Pawn => PAWN_TABLE[index],
+            PieceType::Knight => KNIGHT_TABLE[index],
+            PieceType::Bishop => BISHOP_TABLE[index],
+            PieceType::Rook => ROOK_TABLE[index],
+            PieceType::Queen => QUEEN_TABLE[index],
+            PieceType::King => {
+                kings[color as usize] = Some((index, sign));
+                0
+            }
+        };
+
+        score += sign * (values.value(piece.piece_type()) + bonus);
+    }
+
+    let phase = phase.min(MAX_PHASE);
+    let king_bonus = |index: usize| (KING_MIDDLEGAME_TABLE[index] * phase + KING_ENDGAME_TABLE[index] * (MAX_PHASE - phase)) / MAX_PHASE;
+
+    for (index, sign) in kings.into_iter().flatten() {
+        score += sign * king_bonus(index);
+    }
+
+    score
+}
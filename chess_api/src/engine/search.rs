@@ -0,0 +1,459 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::board::{Board, MoveFilter, MoveUndo};
+use crate::movement::{Move, MoveList};
+use crate::piece::PieceType;
+
+use super::eval::evaluate;
+use super::strength::{next_random, Strength};
+use super::tt::{score_from_table, score_to_table, Bound, Entry, TranspositionTable};
+use super::{BestMove, SearchLimits, MATE, MAX_DEPTH};
+
+
+const INFINITY: i32 = MATE + 1;
+const MAX_PLY: usize = 128;
+
+/// how often time limit and stop flag are checked, in nodes
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+// move ordering scores
+const TT_MOVE_SCORE: i32 = 1_000_000;
+const GOOD_CAPTURE_SCORE: i32 = 100_000;
+const PROMOTION_SCORE: i32 = 95_000;
+const KILLER_SCORE: i32 = 90_000;
+const BAD_CAPTURE_SCORE: i32 = -100_000;
+
+/// # State of one search
+pub(super) struct Searcher<'a> {
+    board: Board,
+    limits: SearchLimits,
+    tt: &'a mut TranspositionTable,
+    /// set from outside to end the search early
+    stop: &'a AtomicBool,
+    start: Instant,
+    nodes: u64,
+    stopped: bool,
+    /// hashes of positions from the start of the game up to the current search node
+    positions: Vec<u64>,
+    killers: [[Option<Move>; 2]; MAX_PLY],
+    history: Box<[[i32; 64]; 64]>,
+    root_best: Option<Move>,
+    eval_noise: i32,
+    blunder_chance: u32,
+    blunder_margin: i32,
+    /// state of random numbers used by weakened engine
+    seed: u64,
+    /// exact score of every root move, only collected when engine may blunder
+    root_scores: Vec<(Move, i32)>
+}
+
+impl<'a> Searcher<'a> {
+    pub fn new(board: &Board, positions: &[u64], limits: SearchLimits, strength: &Strength, seed: u64, tt: &'a mut TranspositionTable, stop: &'a AtomicBool) -> Searcher<'a> {
+        let mut positions = positions.to_vec();
+
+        if positions.last() != Some(&board.hash()) {
+            positions.push(board.hash());
+        }
+
+        Searcher {
+            board: board.clone(),
+            limits,
+            tt,
+            stop,
+            start: Instant::now(),
+            nodes: 0,
+            stopped: false,
+            positions,
+            killers: [[None; 2]; MAX_PLY],
+            history: Box::new([[0; 64]; 64]),
+            root_best: None,
+            eval_noise: strength.eval_noise,
+            blunder_chance: strength.blunder_chance,
+            blunder_margin: strength.blunder_margin,
+            seed,
+            root_scores: Vec::new()
+        }
+    }
+
+    /// # Runs iterative deepening until limits are reached
+    ///
+    /// `info` is called with result of every fully searched iteration
+    ///
+    pub fn run(mut self, info: &mut dyn FnMut(&BestMove)) -> BestMove {
+        let legal = self.board.generate_legal(MoveFilter::All);
+
+        let mut best = BestMove {
+            m: legal.first().copied(),
+            score: 0,
+            depth: 0,
+            nodes: 0,
+            time: Duration::ZERO,
+            pv: legal.first().copied().into_iter().collect()
+        };
+
+        if legal.is_empty() {
+            best.score = if self.board.is_king_attacked(self.board.side_to_move()) { -MATE } else { 0 };
+            return best;
+        }
+
+        let max_depth = self.limits.depth.unwrap_or(MAX_DEPTH).clamp(1, MAX_DEPTH);
+
+        let mut root_scores = Vec::new();
+
+        for depth in 1..=max_depth {
+            self.root_best = None;
+            self.root_scores.clear();
+            let score = self.negamax(depth as i32, 0, -INFINITY, INFINITY);
+
+            if self.stopped {
+                // partially searched iteration is only trusted for its first, already best ordered move
+                if let (Some(m), 1) = (self.root_best, depth) {
+                    best.m = Some(m);
+                    best.pv = vec![m];
+                }
+                break;
+            }
+
+            best.m = self.root_best.or(best.m);
+            best.score = score;
+            best.depth = depth;
+            best.pv = self.principal_variation(depth as usize);
+            best.nodes = self.nodes;
+            best.time = self.start.elapsed();
+            root_scores = std::mem::take(&mut self.root_scores);
+
+            info(&best);
+
+            if score.abs() > MATE - depth as i32 {
+                break;
+            }
+
+            if let Some(time) = self.limits.time {
+                // next iteration would most likely not finish in time
+                if self.start.elapsed() * 2 > time {
+                    break;
+                }
+            }
+        }
+
+        if let Some(m) = self.pick_blunder(&root_scores, best.score) {
+            best.m = Some(m);
+            best.pv = vec![m];
+        }
+
+        best.nodes = self.nodes;
+        best.time = self.start.elapsed();
+        best
+    }
+
+    /// # Randomly picks move not much worse than the best one, according to blunder settings
+    fn pick_blunder(&mut self, root_scores: &[(Move, i32)], best_score: i32) -> Option<Move> {
+        if self.blunder_chance == 0 || next_random(&mut self.seed) % 100 >= self.blunder_chance as u64 {
+            return None;
+        }
+
+        let candidates: Vec<Move> = root_scores.iter()
+            .filter(|(_, score)| *score >= best_score - self.blunder_margin)
+            .map(|(m, _)| *m)
+            .collect();
+
+        if candidates.is_empty() {
+            None
+        } else {
+            Some(candidates[(next_random(&mut self.seed) % candidates.len() as u64) as usize])
+        }
+    }
+
+    /// # Returns evaluation of current position, changed by random noise of weakened engine
+    fn evaluate(&self) -> i32 {
+        let score = evaluate(&self.board);
+
+        if self.eval_noise == 0 {
+            return score;
+        }
+
+        // noise depends only on position, so that transpositions are evaluated same
+        let mut state = self.board.hash() ^ self.seed;
+        let range = 2 * self.eval_noise as u64 + 1;
+
+        score + (next_random(&mut state) % range) as i32 - self.eval_noise
+    }
+
+    /// # Follows best moves stored in transposition table
+    fn principal_variation(&self, depth: usize) -> Vec<Move> {
+        let mut board = self.board.clone();
+        let mut pv: Vec<Move> = self.root_best.into_iter().collect();
+
+        if let Some(m) = self.root_best {
+            board.apply_move(m);
+        }
+
+        while pv.len() < depth {
+            let m = match self.tt.probe(board.hash()).and_then(|entry| entry.best) {
+                Some(m) if board.generate_legal(MoveFilter::All).contains(&m) => m,
+                _ => break
+            };
+
+            board.apply_move(m);
+            pv.push(m);
+        }
+
+        pv
+    }
+
+    fn should_stop(&mut self) -> bool {
+        if self.stopped {
+            return true;
+        }
+
+        if self.limits.nodes.is_some_and(|nodes| self.nodes >= nodes) {
+            self.stopped = true;
+        }
+
+        if self.nodes.is_multiple_of(TIME_CHECK_INTERVAL)
+            && (self.stop.load(Ordering::Relaxed) || self.limits.time.is_some_and(|time| self.start.elapsed() >= time)) {
+            self.stopped = true;
+        }
+
+        self.stopped
+    }
+
+    /// # Returns true if current position is drawn regardless of moves
+    fn is_draw(&self) -> bool {
+        if self.board.is_insufficient_material() {
+            return true;
+        }
+
+        // checkmate takes precedence over the 50-move rule
+        if self.board.halfmove_clock() >= 100 {
+            return !self.board.is_king_attacked(self.board.side_to_move()) || !self.board.generate_legal(MoveFilter::All).is_empty();
+        }
+
+        // positions before last capture or pawn move cannot repeat
+        let reversible = (self.board.halfmove_clock() as usize).min(self.positions.len() - 1);
+        let current = self.board.hash();
+
+        self.positions[self.positions.len() - 1 - reversible..self.positions.len() - 1].contains(&current)
+    }
+
+    fn make_move(&mut self, m: Move) -> MoveUndo {
+        let undo = self.board.apply_move(m);
+        self.positions.push(self.board.hash());
+        undo
+    }
+
+    fn unmake_move(&mut self, undo: MoveUndo) {
+        self.positions.pop();
+        self.board.undo_move(undo);
+    }
+
+    fn negamax(&mut self, depth: i32, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        if self.should_stop() {
+            return 0;
+        }
+
+        self.nodes += 1;
+
+        if ply > 0 && self.is_draw() {
+            return 0;
+        }
+
+        let in_check = self.board.is_king_attacked(self.board.side_to_move());
+        let depth = if in_check { depth + 1 } else { depth };
+
+        if depth <= 0 {
+            return self.quiescence(ply, alpha, beta);
+        }
+
+        if ply >= MAX_PLY - 1 {
+            return self.evaluate();
+        }
+
+        let hash = self.board.hash();
+        let mut tt_move = None;
+
+        if let Some(entry) = self.tt.probe(hash) {
+            tt_move = entry.best;
+            let score = score_from_table(entry.score, ply);
+
+            if ply > 0 && entry.depth >= depth {
+                match entry.bound {
+                    Bound::Exact => return score,
+                    Bound::Lower if score >= beta => return score,
+                    Bound::Upper if score <= alpha => return score,
+                    _ => ()
+                }
+            }
+        }
+
+        let mut moves = self.board.generate_legal(MoveFilter::All);
+
+        if moves.is_empty() {
+            return if in_check { -MATE + ply as i32 } else { 0 };
+        }
+
+        let mut scores = self.order_scores(&moves, tt_move, ply);
+        let original_alpha = alpha;
+        let mut best_score = -INFINITY;
+        let mut best_move = None;
+
+        for i in 0..moves.len() {
+            let m = pick_next(&mut moves, &mut scores, i);
+            let quiet = !self.board.is_capture(m) && m.promotion().is_none();
+
+            let undo = self.make_move(m);
+
+            let score = if ply == 0 && self.blunder_chance > 0 {
+                // every root move needs exact score to choose from
+                let score = -self.negamax(depth - 1, ply + 1, -INFINITY, INFINITY);
+                self.root_scores.push((m, score));
+                score
+            } else if i == 0 {
+                -self.negamax(depth - 1, ply + 1, -beta, -alpha)
+            } else {
+                // principal variation search, other moves are expected to be worse
+                let score = -self.negamax(depth - 1, ply + 1, -alpha - 1, -alpha);
+
+                if score > alpha && score < beta {
+                    -self.negamax(depth - 1, ply + 1, -beta, -alpha)
+                } else { score }
+            };
+
+            self.unmake_move(undo);
+
+            if self.stopped {
+                return 0;
+            }
+
+            if score > best_score {
+                best_score = score;
+                best_move = Some(m);
+
+                if ply == 0 {
+                    self.root_best = Some(m);
+                }
+            }
+
+            alpha = alpha.max(score);
+
+            if alpha >= beta {
+                if quiet {
+                    self.store_killer(m, ply);
+                    self.history[m.start().to_index()][m.end().to_index()] += depth * depth;
+                }
+                break;
+            }
+        }
+
+        let bound = if best_score >= beta {
+            Bound::Lower
+        } else if best_score > original_alpha {
+            Bound::Exact
+        } else { Bound::Upper };
+
+        self.tt.store(Entry { key: hash, depth, score: score_to_table(best_score, ply), bound, best: best_move });
+
+        best_score
+    }
+
+    /// # Searches captures until position is quiet, so that evaluation is not done in the middle of exchange
+    fn quiescence(&mut self, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        if self.should_stop() {
+            return 0;
+        }
+
+        self.nodes += 1;
+
+        if ply >= MAX_PLY - 1 {
+            return self.evaluate();
+        }
+
+        let in_check = self.board.is_king_attacked(self.board.side_to_move());
+
+        let (mut moves, mut best_score) = if in_check {
+            let moves = self.board.generate_legal(MoveFilter::All);
+
+            if moves.is_empty() {
+                return -MATE + ply as i32;
+            }
+
+            (moves, -INFINITY)
+        } else {
+            let stand_pat = self.evaluate();
+
+            if stand_pat >= beta {
+                return stand_pat;
+            }
+
+            alpha = alpha.max(stand_pat);
+
+            let mut moves = self.board.generate_legal(MoveFilter::Captures);
+            moves.retain(|m| self.board.see(*m) >= 0);
+
+            (moves, stand_pat)
+        };
+
+        let mut scores = self.order_scores(&moves, None, ply);
+
+        for i in 0..moves.len() {
+            let m = pick_next(&mut moves, &mut scores, i);
+
+            let undo = self.make_move(m);
+            let score = -self.quiescence(ply + 1, -beta, -alpha);
+            self.unmake_move(undo);
+
+            if self.stopped {
+                return 0;
+            }
+
+            best_score = best_score.max(score);
+            alpha = alpha.max(score);
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best_score
+    }
+
+    fn store_killer(&mut self, m: Move, ply: usize) {
+        if self.killers[ply][0] != Some(m) {
+            self.killers[ply][1] = self.killers[ply][0];
+            self.killers[ply][0] = Some(m);
+        }
+    }
+
+    /// # Returns ordering score for every move, moves with higher scores are searched first
+    fn order_scores(&self, moves: &MoveList, tt_move: Option<Move>, ply: usize) -> [i32; 256] {
+        let mut scores = [0; 256];
+
+        for (score, m) in scores.iter_mut().zip(moves.iter()) {
+            *score = if Some(*m) == tt_move {
+                TT_MOVE_SCORE
+            } else if self.board.is_capture(*m) {
+                let see = self.board.see(*m);
+                if see >= 0 { GOOD_CAPTURE_SCORE + see } else { BAD_CAPTURE_SCORE + see }
+            } else if m.promotion() == Some(PieceType::Queen) {
+                PROMOTION_SCORE
+            } else if self.killers[ply].contains(&Some(*m)) {
+                KILLER_SCORE
+            } else {
+                self.history[m.start().to_index()][m.end().to_index()].min(KILLER_SCORE - 1)
+            };
+        }
+
+        scores
+    }
+}
+
+/// # Moves move with highest score to given position and returns it
+fn pick_next(moves: &mut MoveList, scores: &mut [i32; 256], i: usize) -> Move {
+    let best = (i..moves.len()).max_by_key(|j| scores[*j]).unwrap();
+
+    moves.swap(i, best);
+    scores.swap(i, best);
+
+    moves[i]
+}
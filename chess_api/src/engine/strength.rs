@@ -0,0 +1,104 @@
+use super::{SearchLimits, MAX_DEPTH};
+
+
+/// # Settings weakening the engine, used as levels of computer opponent
+///
+/// ```
+/// use chess_api::engine::Strength;
+///
+/// let weakest = Strength::level(1);
+/// let strongest = Strength::level(9);
+///
+/// assert!(weakest.depth < strongest.depth);
+/// assert!(weakest.eval_noise > 0 && weakest.blunder_chance > 0);
+/// assert_eq!(strongest, Strength::full());
+/// ```
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Strength {
+    /// deepest search iteration
+    pub depth: u32,
+    /// largest number of searched positions per move
+    pub nodes: Option<u64>,
+    /// largest random change of every evaluation, in centipawns
+    pub eval_noise: i32,
+    /// chance of playing random move instead of the best one, in percent
+    pub blunder_chance: u32,
+    /// how much worse than the best move randomly played move can be, in centipawns
+    pub blunder_margin: i32
+}
+
+// depth, nodes, noise, blunder chance and margin of levels 1 to 8, level 9 is full strength
+const LEVELS: [(u32, Option<u64>, i32, u32, i32); 8] = [
+    (1, Some(200),     200, 40, 400),
+    (1, Some(500),     150, 30, 300),
+    (2, Some(1_000),   100, 25, 200),
+    (2, Some(2_000),    60, 15, 150),
+    (3, Some(5_000),    40, 10, 100),
+    (3, Some(10_000),   20,  5,  60),
+    (4, Some(25_000),   10,  2,  30),
+    (5, Some(60_000),    5,  0,   0)
+];
+
+impl Strength {
+    pub const MIN_LEVEL: u32 = 1;
+    pub const MAX_LEVEL: u32 = 9;
+
+    /// # Returns settings of given level, levels outside of 1 - 9 are clamped
+    ///
+    /// level 1 searches one move ahead and often plays random moves,
+    /// every next level searches deeper with less randomness,
+    /// level 9 plays at full strength and is only limited by `SearchLimits`
+    ///
+    pub fn level(level: u32) -> Strength {
+        let level = level.clamp(Strength::MIN_LEVEL, Strength::MAX_LEVEL);
+
+        if level == Strength::MAX_LEVEL {
+            return Strength::full();
+        }
+
+        let (depth, nodes, eval_noise, blunder_chance, blunder_margin) = LEVELS[level as usize - 1];
+
+        Strength { depth, nodes, eval_noise, blunder_chance, blunder_margin }
+    }
+
+    /// # Returns settings without any weakening
+    pub fn full() -> Strength {
+        Strength {
+            depth: MAX_DEPTH,
+            nodes: None,
+            eval_noise: 0,
+            blunder_chance: 0,
+            blunder_margin: 0
+        }
+    }
+
+    /// # Returns given limits tightened by depth and node caps of this strength
+    pub fn restrict(&self, limits: SearchLimits) -> SearchLimits {
+        let nodes = match (limits.nodes, self.nodes) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b)
+        };
+
+        SearchLimits {
+            depth: Some(limits.depth.unwrap_or(MAX_DEPTH).min(self.depth)),
+            nodes,
+            time: limits.time
+        }
+    }
+}
+
+impl Default for Strength {
+    fn default() -> Self {
+        Strength::full()
+    }
+}
+
+/// # Returns next number of SplitMix64 sequence, advancing the state
+pub(super) fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,81 @@
+use crate::movement::Move;
+
+use super::MATE;
+
+
+/// # Kind of bound stored score is
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub(super) enum Bound {
+    /// score is exact
+    Exact,
+    /// real score is at least stored one, search failed high
+    Lower,
+    /// real score is at most stored one, search failed low
+    Upper
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(super) struct Entry {
+    pub key: u64,
+    pub depth: i32,
+    pub score: i32,
+    pub bound: Bound,
+    pub best: Option<Move>
+}
+
+/// # Hash table of already searched positions indexed by `Board::hash`
+pub(super) struct TranspositionTable {
+    entries: Vec<Option<Entry>>
+}
+
+impl TranspositionTable {
+    /// # Creates table taking about given number of megabytes
+    pub fn new(megabytes: usize) -> TranspositionTable {
+        let bytes = megabytes.max(1) * 1024 * 1024;
+        let count = (bytes / std::mem::size_of::<Option<Entry>>()).next_power_of_two() / 2;
+
+        TranspositionTable {
+            entries: vec![None; count.max(1)]
+        }
+    }
+
+    fn index(&self, key: u64) -> usize {
+        (key as usize) & (self.entries.len() - 1)
+    }
+
+    pub fn probe(&self, key: u64) -> Option<&Entry> {
+        self.entries[self.index(key)].as_ref().filter(|entry| entry.key == key)
+    }
+
+    /// # Stores entry, keeping deeper entry of the same position
+    pub fn store(&mut self, entry: Entry) {
+        let index = self.index(entry.key);
+
+        match self.entries[index] {
+            Some(old) if old.key == entry.key && old.depth > entry.depth && entry.bound != Bound::Exact => (),
+            _ => self.entries[index] = Some(entry)
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.iter_mut().for_each(|entry| *entry = None);
+    }
+}
+
+/// # Converts mate score relative to root into score relative to given ply, as stored in the table
+pub(super) fn score_to_table(score: i32, ply: usize) -> i32 {
+    if score > MATE / 2 {
+        score + ply as i32
+    } else if score < -MATE / 2 {
+        score - ply as i32
+    } else { score }
+}
+
+/// # Reverses `score_to_table`
+pub(super) fn score_from_table(score: i32, ply: usize) -> i32 {
+    if score > MATE / 2 {
+        score - ply as i32
+    } else if score < -MATE / 2 {
+        score + ply as i32
+    } else { score }
+}
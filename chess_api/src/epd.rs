@@ -0,0 +1,342 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::board::{Board, FenError, SanError};
+use crate::engine::{Engine, SearchLimits};
+use crate::movement::Move;
+
+
+/// # Reason why EPD record could not be parsed
+#[derive(Debug, PartialEq)]
+pub enum EpdError {
+    /// record has less than 4 position fields
+    MissingFields(usize),
+    /// position fields, with halfmove clock and fullmove number from operations, are not valid FEN
+    Fen(FenError),
+    /// operation without opcode, or opcode not starting with letter
+    InvalidOpcode(String),
+    /// string operand or operation not closed before end of record
+    Unterminated(String),
+    /// operand which does not fit its opcode, e.g. ce which is not a number
+    InvalidOperand { opcode: String, operand: String },
+    /// move of bm, am or pv which is not legal
+    Move { opcode: String, error: SanError }
+}
+
+impl fmt::Display for EpdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EpdError::MissingFields(n) => write!(f, "expected 4 position fields, found {}", n),
+            EpdError::Fen(e) => write!(f, "{}", e),
+            EpdError::InvalidOpcode(s) => write!(f, "invalid opcode '{}'", s),
+            EpdError::Unterminated(opcode) => write!(f, "operation '{}' is not terminated", opcode),
+            EpdError::InvalidOperand { opcode, operand } => write!(f, "invalid operand '{}' of '{}'", operand, opcode),
+            EpdError::Move { opcode, error } => write!(f, "invalid move in '{}': {}", opcode, error)
+        }
+    }
+}
+
+impl std::error::Error for EpdError {}
+
+/// # Extended Position Description, position with operations like best move or comment
+///
+/// standard opcodes are checked when parsing and have typed access,
+/// others are kept with their operands as written
+///
+/// ```
+/// # use chess_api::epd::Epd;
+/// # use chess_api::movement::Move;
+///
+/// let epd: Epd = "6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - bm Rd8#; id \"back rank\"; ce +32765;".parse().unwrap();
+///
+/// assert_eq!(epd.best_moves(), [Move::from_uci("d1d8").unwrap()]);
+/// assert_eq!(epd.id(), Some("back rank"));
+/// assert_eq!(epd.centipawn_evaluation(), Some(32765));
+/// assert_eq!(epd.board.fullmove_number(), 1);
+/// ```
+#[derive(Clone)]
+pub struct Epd {
+    pub board: Board,
+    /// operations in order of appearance, with operands without quotes
+    pub operations: Vec<(String, Vec<String>)>
+}
+
+/// opcodes whose operands are moves played from the position
+const MOVE_LIST_OPCODES: [&str; 2] = ["bm", "am"];
+
+impl Epd {
+    /// # Returns operands of operation with given opcode
+    pub fn operation(&self, opcode: &str) -> Option<&[String]> {
+        self.operations.iter().find(|(name, _)| name == opcode).map(|(_, operands)| operands.as_slice())
+    }
+
+    fn moves(&self, opcode: &str) -> Vec<Move> {
+        // moves were checked when parsing
+        self.operation(opcode).unwrap_or_default().iter().map(|san| self.board.parse_san(san).unwrap()).collect()
+    }
+
+    /// # Returns best moves, `bm`
+    pub fn best_moves(&self) -> Vec<Move> {
+        self.moves("bm")
+    }
+
+    /// # Returns moves to avoid, `am`
+    pub fn avoid_moves(&self) -> Vec<Move> {
+        self.moves("am")
+    }
+
+    /// # Returns position identifier, `id`
+    pub fn id(&self) -> Option<&str> {
+        self.operation("id").and_then(|operands| operands.first()).map(String::as_str)
+    }
+
+    /// # Returns evaluation in centipawns from view of side to move, `ce`
+    pub fn centipawn_evaluation(&self) -> Option<i32> {
+        self.operation("ce").map(|operands| operands[0].parse().unwrap())
+    }
+
+    /// # Returns predicted variation, `pv`, each move played after the previous one
+    pub fn predicted_variation(&self) -> Vec<Move> {
+        let mut board = self.board.clone();
+
+        self.operation("pv").unwrap_or_default().iter().map(|san| {
+            let m = board.parse_san(san).unwrap();
+            board.apply_move(m);
+            m
+        }).collect()
+    }
+
+    /// # Returns comment with given number, `c0` to `c9`
+    pub fn comment(&self, n: u8) -> Option<&str> {
+        self.operation(&format!("c{}", n)).and_then(|operands| operands.first()).map(String::as_str)
+    }
+
+    /// # Returns true if given move is one of best moves and is not one to avoid
+    ///
+    /// positions with neither best moves nor moves to avoid can not be solved
+    ///
+    pub fn is_solved_by(&self, m: Move) -> bool {
+        let best = self.best_moves();
+        let avoid = self.avoid_moves();
+
+        (!best.is_empty() || !avoid.is_empty()) && (best.is_empty() || best.contains(&m)) && !avoid.contains(&m)
+    }
+
+    /// # Checks operands of standard opcodes, so that typed access can not fail
+    fn check_operations(&self) -> Result<(), EpdError> {
+        for (opcode, operands) in &self.operations {
+            let invalid = |operand: &str| EpdError::InvalidOperand { opcode: opcode.clone(), operand: operand.to_string() };
+            let move_error = |error| EpdError::Move { opcode: opcode.clone(), error };
+
+            match opcode.as_str() {
+                _ if MOVE_LIST_OPCODES.contains(&opcode.as_str()) => for san in operands {
+                    self.board.parse_san(san).map_err(move_error)?;
+                },
+                "pv" => {
+                    let mut board = self.board.clone();
+
+                    for san in operands {
+                        board.apply_move(board.parse_san(san).map_err(move_error)?);
+                    }
+                },
+                "ce" => match operands.as_slice() {
+                    [value] if value.parse::<i32>().is_ok() => (),
+                    _ => return Err(invalid(&operands.join(" ")))
+                },
+                _ => ()
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// # Splits operations part of EPD record, operands in quotes may contain spaces and semicolons
+fn parse_operations(s: &str) -> Result<Vec<(String, Vec<String>)>, EpdError> {
+    let mut operations = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        if chars.peek().is_none() {
+            return Ok(operations);
+        }
+
+        let mut opcode = String::new();
+
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != ';') {
+            opcode.push(c);
+        }
+
+        if !opcode.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return Err(EpdError::InvalidOpcode(opcode));
+        }
+
+        let mut operands = Vec::new();
+
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+            let mut operand = String::new();
+
+            match chars.next() {
+                Some(';') => break,
+                Some('"') => loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => operand.push(c),
+                        None => return Err(EpdError::Unterminated(opcode))
+                    }
+                },
+                Some(c) => {
+                    operand.push(c);
+
+                    while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != ';') {
+                        operand.push(c);
+                    }
+                },
+                None => return Err(EpdError::Unterminated(opcode))
+            }
+
+            operands.push(operand);
+        }
+
+        operations.push((opcode, operands));
+    }
+}
+
+impl FromStr for Epd {
+    type Err = EpdError;
+
+    /// # Parses EPD record, halfmove clock and fullmove number are taken from hmvc and fmvn operations
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split_whitespace();
+        let position: Vec<&str> = fields.by_ref().take(4).collect();
+
+        if position.len() < 4 {
+            return Err(EpdError::MissingFields(position.len()));
+        }
+
+        // operations start after the fourth field
+        let rest = s.trim_start();
+        let rest = position.iter().fold(rest, |rest, field| rest.trim_start()[field.len()..].trim_start());
+
+        let operations = parse_operations(rest)?;
+        let counter = |opcode: &str, default: &str| {
+            operations.iter()
+                .find(|(name, _)| name == opcode)
+                .and_then(|(_, operands)| operands.first().cloned())
+                .unwrap_or(default.to_string())
+        };
+
+        let fen = format!("{} {} {}", position.join(" "), counter("hmvc", "0"), counter("fmvn", "1"));
+        let epd = Epd { board: Board::from_fen(&fen).map_err(EpdError::Fen)?, operations };
+
+        epd.check_operations()?;
+        Ok(epd)
+    }
+}
+
+impl fmt::Display for Epd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fen = self.board.to_fen();
+        let position: Vec<&str> = fen.split(' ').take(4).collect();
+
+        write!(f, "{}", position.join(" "))?;
+
+        for (opcode, operands) in &self.operations {
+            write!(f, " {}", opcode)?;
+
+            for operand in operands {
+                if operand.is_empty() || operand.contains(|c: char| c.is_whitespace() || c == ';') || is_comment(opcode) || opcode == "id" {
+                    write!(f, " \"{}\"", operand)?;
+                } else {
+                    write!(f, " {}", operand)?;
+                }
+            }
+
+            write!(f, ";")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// # Returns true for comment opcodes `c0` to `c9`
+fn is_comment(opcode: &str) -> bool {
+    opcode.len() == 2 && opcode.starts_with('c') && opcode.as_bytes()[1].is_ascii_digit()
+}
+
+/// # Reads every EPD record of text, empty lines and lines starting with `#` are skipped
+///
+/// errors contain line number, counted from 1
+///
+pub fn read_epd(text: &str) -> Result<Vec<Epd>, (usize, EpdError)> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(number, line)| line.parse().map_err(|e| (number + 1, e)))
+        .collect()
+}
+
+/// # Answer of engine to one test position
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionResult {
+    /// id operation of the position, or its number in the suite counted from 1
+    pub id: String,
+    pub found: Option<Move>,
+    pub solved: bool
+}
+
+/// # Results of test suite searched with one time limit
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuiteReport {
+    pub time: Duration,
+    pub results: Vec<PositionResult>
+}
+
+impl SuiteReport {
+    pub fn solved(&self) -> usize {
+        self.results.iter().filter(|result| result.solved).count()
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &PositionResult> {
+        self.results.iter().filter(|result| !result.solved)
+    }
+}
+
+/// # Searches every position with given time and checks if the engine found best move
+///
+/// hash table is cleared before every position, so that results do not depend on order,
+/// `progress` is called after every position
+///
+/// ```
+/// use std::time::Duration;
+/// use chess_api::engine::Engine;
+/// use chess_api::epd::{read_epd, run_suite};
+///
+/// let suite = read_epd("6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - bm Rd8#; id \"mate\";\n\
+///     4k3/8/8/8/8/8/8/R3K2R w KQ - am Ra8; id \"not check\";").unwrap();
+///
+/// let report = run_suite(&mut Engine::new(), &suite, Duration::from_millis(100), |_| ());
+/// assert_eq!(report.solved(), 2);
+/// ```
+pub fn run_suite<F: FnMut(&PositionResult)>(engine: &mut Engine, positions: &[Epd], time: Duration, mut progress: F) -> SuiteReport {
+    let results = positions.iter().enumerate().map(|(i, epd)| {
+        engine.clear();
+
+        let found = engine.search(&epd.board, SearchLimits::time(time)).get_move();
+        let result = PositionResult {
+            id: epd.id().map(str::to_string).unwrap_or_else(|| (i + 1).to_string()),
+            found,
+            solved: found.is_some_and(|m| epd.is_solved_by(m))
+        };
+
+        progress(&result);
+        result
+    }).collect();
+
+    SuiteReport { time, results }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+
+use crate::board::*;
+use crate::movement::*;
+
+
+/// # Move played in a game
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameMove {
+    m: Move,
+    san: String,
+    state: PostMoveState
+}
+
+impl GameMove {
+    pub fn get_move(&self) -> Move {
+        self.m
+    }
+
+    pub fn san(&self) -> &str {
+        &self.san
+    }
+
+    /// # Returns state of the game right after the move
+    pub fn state(&self) -> PostMoveState {
+        self.state
+    }
+}
+
+/// # Reason why move could not be played in a game
+#[derive(Debug, PartialEq)]
+pub enum GameError {
+    Move(MoveFailReason),
+    San(SanError)
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::Move(reason) => write!(f, "move is not possible: {:?}", reason),
+            GameError::San(e) => write!(f, "{}", e)
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// # Game record
+///
+/// wraps board and remembers every played move, so that moves can be taken back and replayed
+///
+/// ```
+/// # use chess_api::game::Game;
+///
+/// let mut game = Game::new();
+///
+/// assert!(game.play_san("e4").is_ok());
+/// assert!(game.play_san("e5").is_ok());
+/// assert_eq!(game.moves().map(|m| m.san()).collect::<Vec<_>>(), ["e4", "e5"]);
+///
+/// assert!(game.undo_move().is_some());
+/// assert_eq!(game.moves().count(), 1);
+///
+/// assert!(game.redo_move().is_some());
+/// assert_eq!(game.moves().count(), 2);
+/// ```
+#[derive(Clone)]
+pub struct Game {
+    board: Board,
+    history: Vec<(GameMove, MoveUndo)>,
+    undone: Vec<GameMove>,
+    positions: Vec<u64>
+}
+
+
+impl Game {
+    /// # Creates game with standard starting position
+    pub fn new() -> Game {
+        Game::from_board(Board::new())
+    }
+
+    /// # Creates game starting at given position
+    pub fn from_board(board: Board) -> Game {
+        Game {
+            positions: vec![board.hash()],
+            board,
+            history: vec![],
+            undone: vec![]
+        }
+    }
+
+    /// # Returns board with current position
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// # Returns board with position the game started from
+    ///
+    /// ```
+    /// # use chess_api::game::Game;
+    /// # use chess_api::board::Board;
+    ///
+    /// let mut game = Game::new();
+    /// assert!(game.play_san("d4").is_ok());
+    ///
+    /// assert_eq!(game.start_board().to_fen(), Board::new().to_fen());
+    /// ```
+    pub fn start_board(&self) -> Board {
+        let mut board = self.board.clone();
+
+        for (_, undo) in self.history.iter().rev() {
+            board.undo_move(undo.clone());
+        }
+
+        board
+    }
+
+    /// # Returns iterator over played moves, from the first one
+    pub fn moves(&self) -> impl Iterator<Item = &GameMove> {
+        self.history.iter().map(|(game_move, _)| game_move)
+    }
+
+    /// # Returns last played move
+    pub fn last_move(&self) -> Option<&GameMove> {
+        self.history.last().map(|(game_move, _)| game_move)
+    }
+
+    /// # Plays move after checking if it is possible
+    ///
+    /// moves which were taken back can no longer be replayed
+    ///
+    pub fn play(&mut self, m: Move) -> Result<PostMoveState, MoveFailReason> {
+        let state = self.record(m)?;
+        self.undone.clear();
+
+        Ok(state)
+    }
+
+    /// # Plays move written in SAN
+    pub fn play_san(&mut self, san: &str) -> Result<PostMoveState, GameError> {
+        let m = self.board.parse_san(san).map_err(GameError::San)?;
+        self.play(m).map_err(GameError::Move)
+    }
+
+    /// # Takes back last played move
+    ///
+    /// returns move which was taken back
+    ///
+    pub fn undo_move(&mut self) -> Option<Move> {
+        let (game_move, undo) = self.history.pop()?;
+        let m = game_move.get_move();
+
+        self.board.undo_move(undo);
+        self.positions.pop();
+        self.undone.push(game_move);
+
+        Some(m)
+    }
+
+    /// # Replays last move taken back with `undo_move`
+    ///
+    /// returns state of the game after replayed move
+    ///
+    pub fn redo_move(&mut self) -> Option<PostMoveState> {
+        let game_move = self.undone.pop()?;
+        self.record(game_move.get_move()).ok()
+    }
+
+    fn record(&mut self, m: Move) -> Result<PostMoveState, MoveFailReason> {
+        self.board.check_move_possibility(m)?;
+
+        let san = self.board.to_san(m);
+        let (mut state, undo) = self.board.perform_move_with_undo(m)?;
+
+        self.positions.push(self.board.hash());
+
+        if matches!(state, PostMoveState::Normal | PostMoveState::Check) && self.repetition_count() >= 5 {
+            state = PostMoveState::Draw(DrawReason::FivefoldRepetition);
+        }
+
+        self.history.push((GameMove { m, san, state }, undo));
+
+        Ok(state)
+    }
+
+    /// # Returns hashes of every position in the game, from the starting one
+    pub fn position_hashes(&self) -> &[u64] {
+        &self.positions
+    }
+
+    /// # Returns how many times current position has occurred in the game
+    ///
+    /// positions are compared by `Board::hash`, so they are same when same player is to move,
+    /// pieces stand on same squares and both players have same castling and en passant possibilities
+    ///
+    pub fn repetition_count(&self) -> usize {
+        let current = self.positions.last().unwrap();
+        self.positions.iter().filter(|position| *position == current).count()
+    }
+
+    /// # Returns draw which can be claimed by player who makes next move
+    ///
+    /// ```
+    /// # use chess_api::game::Game;
+    /// # use chess_api::board::DrawReason;
+    ///
+    /// let mut game = Game::new();
+    ///
+    /// for san in ["Nf3", "Nf6", "Ng1", "Ng8", "Nf3", "Nf6", "Ng1"] {
+    ///     assert!(game.play_san(san).is_ok());
+    /// }
+    ///
+    /// assert_eq!(game.claimable_draw(), None);
+    /// assert!(game.play_san("Ng8").is_ok());
+    /// assert_eq!(game.claimable_draw(), Some(DrawReason::ThreefoldRepetition));
+    /// ```
+    pub fn claimable_draw(&self) -> Option<DrawReason> {
+        if self.repetition_count() >= 3 {
+            Some(DrawReason::ThreefoldRepetition)
+        } else {
+            self.board.claimable_draw()
+        }
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
@@ -1,4 +1,13 @@
 pub mod board;
 pub mod piece;
 pub mod movement;
+pub mod game;
+pub mod bitboard;
 
+pub mod engine;
+
+pub mod uci;
+pub mod cecp;
+pub mod pgn;
+pub mod tree;
+pub mod epd;
@@ -1,3 +1,27 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use crate::piece::PieceType;
+
+/// # Reason why UCI string could not be parsed
+#[derive(Debug, PartialEq)]
+pub enum UciError {
+    InvalidSquare(String),
+    InvalidMove(String)
+}
+
+impl fmt::Display for UciError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UciError::InvalidSquare(s) => write!(f, "'{}' is not a valid square", s),
+            UciError::InvalidMove(s) => write!(f, "'{}' is not a valid move", s)
+        }
+    }
+}
+
+impl std::error::Error for UciError {}
+
 /// # Move's square struct
 ///
 /// holds information about move's start or end
@@ -44,6 +68,23 @@ impl Square {
         }
     }
 
+    /// # Parses square written in UCI notation
+    ///
+    /// ```
+    /// use chess_api::movement::Square;
+    ///
+    /// assert_eq!(Square::from_uci("e4").unwrap(), Square::new(4, 3));
+    /// assert!(Square::from_uci("i9").is_err());
+    /// ```
+    pub fn from_uci(s: &str) -> Result<Square, UciError> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(file @ 'a'..='h'), Some(rank @ '1'..='8'), None) => Ok(Square::new(file as u8 - b'a', rank as u8 - b'1')),
+            _ => Err(UciError::InvalidSquare(s.to_string()))
+        }
+    }
+
     pub fn to_uci(&self) -> String {
         format!("{}{}", "abcdefgh".chars().nth(self.x.into()).unwrap(), self.y + 1)
     }
@@ -61,10 +102,11 @@ fn min_max<T: PartialOrd>(a: T, b: T) -> (T, T) {
     if a < b { (a, b) } else { (b, a) }
 }
 
-#[derive(Copy, Clone)]
+#[derive(PartialEq, Copy, Clone, Debug)]
 pub struct Move {
     start: Square,
     end: Square,
+    promotion: Option<PieceType>,
 }
 
 impl Move {
@@ -72,13 +114,68 @@ impl Move {
         assert_ne!(start, end);
 
         Move {
-            start, end
+            start, end, promotion: None
+        }
+    }
+
+    /// # Creates pawn's move with promotion
+    ///
+    /// ```
+    /// use chess_api::movement::{Move, Square};
+    /// use chess_api::piece::PieceType;
+    ///
+    /// let m = Move::new_promotion(Square::new(4, 6), Square::new(4, 7), PieceType::Queen);
+    /// assert_eq!(m.to_uci(), "e7e8q");
+    /// ```
+    pub fn new_promotion(start: Square, end: Square, promotion: PieceType) -> Move {
+        assert_ne!(start, end);
+
+        Move {
+            start, end, promotion: Some(promotion)
+        }
+    }
+
+    /// # Parses move written in UCI notation
+    ///
+    /// ```
+    /// use chess_api::movement::{Move, Square};
+    /// use chess_api::piece::PieceType;
+    ///
+    /// assert_eq!(Move::from_uci("e2e4").unwrap(), Move::new(Square::new(4, 1), Square::new(4, 3)));
+    /// assert_eq!(Move::from_uci("a7a8q").unwrap().promotion(), Some(PieceType::Queen));
+    /// assert!(Move::from_uci("e2e2").is_err());
+    /// ```
+    pub fn from_uci(s: &str) -> Result<Move, UciError> {
+        let error = || UciError::InvalidMove(s.to_string());
+
+        if !s.is_ascii() || s.len() < 4 || s.len() > 5 {
+            return Err(error());
+        }
+
+        let start = Square::from_uci(&s[0..2]).map_err(|_| error())?;
+        let end = Square::from_uci(&s[2..4]).map_err(|_| error())?;
+
+        if start == end {
+            return Err(error());
+        }
+
+        match s[4..].chars().next() {
+            Some(c) => match PieceType::from_uci(c) {
+                Some(promotion) => Ok(Move::new_promotion(start, end, promotion)),
+                None => Err(error())
+            },
+            None => Ok(Move::new(start, end))
         }
     }
 
     pub fn to_uci(&self) -> String {
         let mut result = self.start.to_uci();
         result.push_str(&self.end.to_uci());
+
+        if let Some(promotion) = self.promotion {
+            result.push(promotion.to_uci());
+        }
+
         result
     }
 
@@ -115,6 +212,156 @@ impl Move {
     pub fn end(&self) -> Square {
         self.end
     }
+
+    pub fn promotion(&self) -> Option<PieceType> {
+        self.promotion
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_uci())
+    }
+}
+
+impl FromStr for Square {
+    type Err = UciError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Square::from_uci(s)
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_uci())
+    }
+}
+
+impl FromStr for Move {
+    type Err = UciError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Move::from_uci(s)
+    }
+}
+
+/// # Upper bound for number of moves in any position
+const MAX_MOVES: usize = 256;
+
+/// placeholder filling unused part of `MoveList`
+const NULL_MOVE: Move = Move { start: Square { x: 0, y: 0 }, end: Square { x: 0, y: 0 }, promotion: None };
+
+/// # List of moves stored without heap allocation
+///
+/// dereferences to slice of moves, so it can be iterated, indexed and sorted
+///
+/// ```
+/// use chess_api::movement::{Move, MoveList, Square};
+///
+/// let mut list = MoveList::new();
+///
+/// list.push(Move::new(Square::new(4, 1), Square::new(4, 3)));
+/// list.push(Move::new(Square::new(6, 0), Square::new(5, 2)));
+///
+/// assert_eq!(list.len(), 2);
+/// assert_eq!(list[0].to_uci(), "e2e4");
+///
+/// list.retain(|m| m.start() == Square::new(6, 0));
+/// assert_eq!(list.iter().map(Move::to_uci).collect::<Vec<_>>(), ["g1f3"]);
+/// ```
+#[derive(Copy, Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    len: usize
+}
+
+impl MoveList {
+    pub fn new() -> MoveList {
+        MoveList {
+            moves: [NULL_MOVE; MAX_MOVES],
+            len: 0
+        }
+    }
+
+    /// # Appends move to the list
+    ///
+    /// panics if list is full
+    ///
+    pub fn push(&mut self, m: Move) {
+        assert!(self.len < MAX_MOVES);
+
+        self.moves[self.len] = m;
+        self.len += 1;
+    }
+
+    /// # Keeps only moves for which given predicate returns true, preserving order
+    pub fn retain<F: FnMut(&Move) -> bool>(&mut self, mut f: F) {
+        let mut kept = 0;
+
+        for i in 0..self.len {
+            if f(&self.moves[i]) {
+                self.moves[kept] = self.moves[i];
+                kept += 1;
+            }
+        }
+
+        self.len = kept;
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        MoveList::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &Self::Target {
+        &self.moves[..self.len]
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl PartialEq for MoveList {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = std::iter::Take<std::array::IntoIter<Move, MAX_MOVES>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.moves.into_iter().take(self.len)
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 pub struct LineMovement {
@@ -207,6 +454,31 @@ mod test {
 
         let s = Move::new(Square::new(1, 2), Square::new(3, 4));
         assert_eq!(s.to_uci(), "b3d5");
+
+        let s = Move::new_promotion(Square::new(0, 1), Square::new(1, 0), PieceType::Knight);
+        assert_eq!(s.to_uci(), "a2b1n");
+    }
+
+    #[test]
+    fn uci_parse_test() {
+        assert_eq!("a1".parse::<Square>(), Ok(Square::new(0, 0)));
+        assert_eq!("h8".parse::<Square>(), Ok(Square::new(7, 7)));
+        assert_eq!("a0".parse::<Square>(), Err(UciError::InvalidSquare("a0".to_string())));
+        assert_eq!("a".parse::<Square>(), Err(UciError::InvalidSquare("a".to_string())));
+        assert_eq!("a1b".parse::<Square>(), Err(UciError::InvalidSquare("a1b".to_string())));
+
+        assert_eq!("b3d5".parse::<Move>(), Ok(Move::new(Square::new(1, 2), Square::new(3, 4))));
+        assert_eq!("a2b1n".parse::<Move>(), Ok(Move::new_promotion(Square::new(0, 1), Square::new(1, 0), PieceType::Knight)));
+        assert_eq!("a2b1x".parse::<Move>(), Err(UciError::InvalidMove("a2b1x".to_string())));
+        assert_eq!("a2b".parse::<Move>(), Err(UciError::InvalidMove("a2b".to_string())));
+        assert_eq!("a2b1qq".parse::<Move>(), Err(UciError::InvalidMove("a2b1qq".to_string())));
+        assert_eq!("é2b1".parse::<Move>(), Err(UciError::InvalidMove("é2b1".to_string())));
+
+        for uci in ["e2e4", "e7e8q", "h1a8"] {
+            assert_eq!(uci.parse::<Move>().unwrap().to_string(), uci);
+        }
+
+        assert_eq!(Square::new(4, 3).to_string(), "e4");
     }
 
     #[test]
@@ -0,0 +1,673 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::board::{Board, FenError, PostMoveState, SanError};
+use crate::game::Game;
+use crate::piece::PieceColor;
+use crate::movement::Move;
+
+
+/// # What was wrong with PGN text
+#[derive(Debug, PartialEq)]
+pub enum PgnErrorKind {
+    /// character which cannot start any token
+    UnexpectedCharacter(char),
+    /// tag pair which is not `[Name "value"]`
+    InvalidTag,
+    /// `{` comment or tag value is not closed before end of text
+    Unterminated,
+    /// NAG `$` not followed by number up to 255
+    InvalidNag(String),
+    /// annotation or variation which does not follow any move
+    NoMove,
+    /// `)` without matching `(`
+    UnexpectedVariationEnd,
+    /// `(` without matching `)` before end of game
+    UnclosedVariation,
+    /// result token inside variation
+    ResultInVariation(String),
+    /// move which is not valid SAN or not legal in its position
+    Move(SanError),
+    /// FEN tag with invalid position
+    Fen(FenError)
+}
+
+impl fmt::Display for PgnErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PgnErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character '{}'", c),
+            PgnErrorKind::InvalidTag => write!(f, "invalid tag pair"),
+            PgnErrorKind::Unterminated => write!(f, "unterminated comment or string"),
+            PgnErrorKind::InvalidNag(s) => write!(f, "invalid NAG '{}'", s),
+            PgnErrorKind::NoMove => write!(f, "annotation or variation before the first move"),
+            PgnErrorKind::UnexpectedVariationEnd => write!(f, "')' without matching '('"),
+            PgnErrorKind::UnclosedVariation => write!(f, "'(' without matching ')'"),
+            PgnErrorKind::ResultInVariation(result) => write!(f, "result '{}' inside variation", result),
+            PgnErrorKind::Move(e) => write!(f, "{}", e),
+            PgnErrorKind::Fen(e) => write!(f, "invalid FEN tag: {}", e)
+        }
+    }
+}
+
+/// # Reason why PGN text could not be read, with position where the problem starts
+///
+/// lines and columns are numbered from 1, columns count characters
+///
+#[derive(Debug, PartialEq)]
+pub struct PgnError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: PgnErrorKind
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+/// # Move of PGN movetext with its annotations
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgnMove {
+    pub m: Move,
+    /// SAN as written in the text
+    pub san: String,
+    /// numeric annotation glyphs, suffixes like `!?` are turned into NAGs too
+    pub nags: Vec<u8>,
+    /// comments written before the move, only possible at start of a game or variation
+    pub comments_before: Vec<String>,
+    /// comments written after the move
+    pub comments: Vec<String>,
+    /// alternatives to this move, each played from position before it
+    pub variations: Vec<Vec<PgnMove>>
+}
+
+impl PgnMove {
+    fn new(m: Move, san: String) -> PgnMove {
+        PgnMove {
+            m,
+            san,
+            nags: Vec::new(),
+            comments_before: Vec::new(),
+            comments: Vec::new(),
+            variations: Vec::new()
+        }
+    }
+}
+
+/// # Game read from PGN
+///
+/// ```
+/// # use chess_api::pgn::PgnGame;
+///
+/// let game: PgnGame = "[White \"Morphy\"]\n\n1. e4 e5 2. Nf3 $1 {the usual} (2. f4) d6 1-0".parse().unwrap();
+///
+/// assert_eq!(game.tag("White"), Some("Morphy"));
+/// assert_eq!(game.moves.len(), 4);
+/// assert_eq!(game.moves[2].nags, [1]);
+/// assert_eq!(game.moves[2].variations[0][0].san, "f4");
+/// assert_eq!(game.result, "1-0");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgnGame {
+    /// tag pairs in order of appearance
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<PgnMove>,
+    /// game termination marker, `*` when missing
+    pub result: String
+}
+
+impl PgnGame {
+    /// # Creates record of given game, finished or not
+    ///
+    /// seven tag roster is filled with unknown values except result, which is decided by the game state,
+    /// games not starting from the standard position get FEN and SetUp tags
+    ///
+    /// ```
+    /// # use chess_api::game::Game;
+    /// # use chess_api::pgn::PgnGame;
+    ///
+    /// let mut game = Game::new();
+    ///
+    /// for san in ["f3", "e5", "g4", "Qh4#"] {
+    ///     assert!(game.play_san(san).is_ok());
+    /// }
+    ///
+    /// let mut pgn = PgnGame::from_game(&game);
+    /// pgn.set_tag("White", "Fool");
+    ///
+    /// assert_eq!(pgn.to_string(), "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n\
+    ///     [White \"Fool\"]\n[Black \"?\"]\n[Result \"0-1\"]\n\n1. f3 e5 2. g4 Qh4# 0-1\n");
+    /// ```
+    pub fn from_game(game: &Game) -> PgnGame {
+        let start = game.start_board();
+
+        let result = match game.last_move().map(|m| m.state()) {
+            Some(PostMoveState::Checkmate) => match game.board().side_to_move() {
+                PieceColor::WHITE => "0-1",
+                PieceColor::BLACK => "1-0"
+            },
+            Some(PostMoveState::Stelemate) | Some(PostMoveState::Draw(_)) => "1/2-1/2",
+            _ => "*"
+        };
+
+        let mut tags: Vec<(String, String)> = [("Event", "?"), ("Site", "?"), ("Date", "????.??.??"), ("Round", "?"), ("White", "?"), ("Black", "?"), ("Result", result)]
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        if start.is_chess960() {
+            tags.push(("Variant".to_string(), "Chess960".to_string()));
+        }
+
+        if start.is_chess960() || start.to_fen() != Board::new().to_fen() {
+            tags.push(("SetUp".to_string(), "1".to_string()));
+            tags.push(("FEN".to_string(), start.to_fen()));
+        }
+
+        PgnGame {
+            tags,
+            moves: game.moves().map(|m| PgnMove::new(m.get_move(), m.san().to_string())).collect(),
+            result: result.to_string()
+        }
+    }
+
+    /// # Sets value of tag, tag is added after the others when missing
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string()))
+        }
+    }
+
+    /// # Sets result, e.g. after resignation, together with Result tag
+    pub fn set_result(&mut self, result: &str) {
+        self.result = result.to_string();
+        self.set_tag("Result", result);
+    }
+
+    /// # Adds `[%clk]` comment with remaining time of player to every mainline move, from the first one
+    pub fn set_clocks(&mut self, clocks: &[Duration]) {
+        for (pgn_move, clock) in self.moves.iter_mut().zip(clocks) {
+            let seconds = clock.as_secs();
+            pgn_move.comments.push(format!("[%clk {}:{:02}:{:02}]", seconds / 3600, seconds / 60 % 60, seconds % 60));
+        }
+    }
+
+    /// # Returns value of tag with given name
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == name).map(|(_, value)| value.as_str())
+    }
+
+    /// # Returns position the game starts from, given by FEN tag or the standard one
+    ///
+    /// board is switched to Chess960 by Variant tag
+    ///
+    pub fn start_board(&self) -> Board {
+        let mut board = match self.tag("FEN") {
+            // position was checked while reading
+            Some(fen) => Board::from_fen(fen).unwrap(),
+            None => Board::new()
+        };
+
+        if is_chess960(&self.tags) {
+            board.set_chess960(true);
+        }
+
+        board
+    }
+
+    /// # Returns game with mainline moves played, variations and annotations are left out
+    pub fn to_game(&self) -> Game {
+        let mut game = Game::from_board(self.start_board());
+
+        for pgn_move in &self.moves {
+            // every move was checked while reading
+            game.play(pgn_move.m).unwrap();
+        }
+
+        game
+    }
+}
+
+impl FromStr for PgnGame {
+    type Err = PgnError;
+
+    /// # Reads the first game of PGN text
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut reader = PgnReader::new(s);
+
+        match reader.next() {
+            Some(game) => game,
+            None => Ok(PgnGame { tags: Vec::new(), moves: Vec::new(), result: "*".to_string() })
+        }
+    }
+}
+
+/// # Writes game in PGN export format
+///
+/// moves are written in SAN with annotations and variations, movetext is wrapped at 80 columns
+///
+impl fmt::Display for PgnGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (name, value) in &self.tags {
+            writeln!(f, "[{} \"{}\"]", name, value.replace('\\', "\\\\").replace('"', "\\\""))?;
+        }
+
+        if !self.tags.is_empty() {
+            writeln!(f)?;
+        }
+
+        let mut tokens = Vec::new();
+        write_line(&mut self.start_board(), &self.moves, &mut tokens);
+        tokens.push(self.result.clone());
+
+        let mut line_length = 0;
+
+        for token in tokens {
+            if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+                writeln!(f)?;
+                line_length = 0;
+            }
+
+            if line_length > 0 {
+                write!(f, " ")?;
+                line_length += 1;
+            }
+
+            write!(f, "{}", token)?;
+            line_length += token.len();
+        }
+
+        writeln!(f)
+    }
+}
+
+const MAX_LINE_LENGTH: usize = 80;
+
+/// # Adds tokens of movetext of given line, black moves get number at start and after comments or variations
+fn write_line(board: &mut Board, moves: &[PgnMove], tokens: &mut Vec<String>) {
+    let mut interrupted = true;
+
+    for pgn_move in moves {
+        write_comments(&pgn_move.comments_before, tokens);
+
+        match board.side_to_move() {
+            PieceColor::WHITE => tokens.push(format!("{}.", board.fullmove_number())),
+            PieceColor::BLACK if interrupted || !pgn_move.comments_before.is_empty() => tokens.push(format!("{}...", board.fullmove_number())),
+            PieceColor::BLACK => ()
+        }
+
+        tokens.push(board.to_san(pgn_move.m));
+        tokens.extend(pgn_move.nags.iter().map(|nag| format!("${}", nag)));
+        write_comments(&pgn_move.comments, tokens);
+
+        for variation in &pgn_move.variations {
+            let first = tokens.len();
+            write_line(&mut board.clone(), variation, tokens);
+
+            // empty variations are left out
+            if tokens.len() > first {
+                tokens[first].insert(0, '(');
+                tokens.last_mut().unwrap().push(')');
+            }
+        }
+
+        interrupted = !pgn_move.comments.is_empty() || !pgn_move.variations.is_empty();
+        board.apply_move(pgn_move.m);
+    }
+}
+
+/// # Returns true if Variant tag names Chess960, under any of its usual names
+fn is_chess960(tags: &[(String, String)]) -> bool {
+    tags.iter()
+        .filter(|(name, _)| name == "Variant")
+        .map(|(_, value)| value.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_lowercase())
+        .any(|variant| ["chess960", "fischerandom", "fischerrandom"].contains(&variant.as_str()))
+}
+
+/// # Adds comments split into words, so that they can be wrapped
+fn write_comments(comments: &[String], tokens: &mut Vec<String>) {
+    for comment in comments {
+        let first = tokens.len();
+        tokens.extend(comment.split_whitespace().map(str::to_string));
+
+        if tokens.len() == first {
+            tokens.push(String::new());
+        }
+
+        tokens[first].insert(0, '{');
+        tokens.last_mut().unwrap().push('}');
+    }
+}
+
+/// # Reads every game of PGN text
+///
+/// ```
+/// # use chess_api::pgn::read_pgn;
+///
+/// let games = read_pgn("[Event \"a\"]\n\n1. d4 *\n\n[Event \"b\"]\n\n1. c4 *\n").unwrap();
+///
+/// assert_eq!(games.len(), 2);
+/// assert_eq!(games[1].tag("Event"), Some("b"));
+/// ```
+pub fn read_pgn(text: &str) -> Result<Vec<PgnGame>, PgnError> {
+    PgnReader::new(text).collect()
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Token {
+    TagStart,
+    TagEnd,
+    Str(String),
+    /// move number indication like `12.` or `12...`, number is not checked
+    MoveNumber,
+    /// SAN, result or tag name
+    Symbol(String),
+    Nag(u8),
+    Comment(String),
+    VariationStart,
+    VariationEnd
+}
+
+/// # Splits PGN text into tokens, remembering where each of them starts
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+    peeked: Option<(Token, usize, usize)>
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "_+#=:-/".contains(c)
+}
+
+fn suffix_nag(suffix: &str) -> Option<u8> {
+    match suffix {
+        "!" => Some(1),
+        "?" => Some(2),
+        "!!" => Some(3),
+        "??" => Some(4),
+        "!?" => Some(5),
+        "?!" => Some(6),
+        _ => None
+    }
+}
+
+impl<'a> Lexer<'a> {
+    fn new(text: &'a str) -> Lexer<'a> {
+        Lexer { chars: text.chars().peekable(), line: 1, column: 1, peeked: None }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(c)
+    }
+
+    fn take_while<P: Fn(char) -> bool>(&mut self, predicate: P) -> String {
+        let mut taken = String::new();
+
+        while let Some(c) = self.chars.peek().copied().filter(|c| predicate(*c)) {
+            taken.push(c);
+            self.bump();
+        }
+
+        taken
+    }
+
+    fn error(&self, line: usize, column: usize, kind: PgnErrorKind) -> PgnError {
+        PgnError { line, column, kind }
+    }
+
+    fn peek(&mut self) -> Result<Option<&(Token, usize, usize)>, PgnError> {
+        if self.peeked.is_none() {
+            self.peeked = self.read()?;
+        }
+
+        Ok(self.peeked.as_ref())
+    }
+
+    /// # Returns next token with its line and column
+    fn next(&mut self) -> Result<Option<(Token, usize, usize)>, PgnError> {
+        match self.peeked.take() {
+            Some(token) => Ok(Some(token)),
+            None => self.read()
+        }
+    }
+
+    fn read(&mut self) -> Result<Option<(Token, usize, usize)>, PgnError> {
+        loop {
+            let (line, column) = (self.line, self.column);
+
+            let c = match self.chars.peek() {
+                Some(c) => *c,
+                None => return Ok(None)
+            };
+
+            let token = match c {
+                _ if c.is_whitespace() => {
+                    self.bump();
+                    continue;
+                },
+                // escape mechanism, the whole line is skipped
+                '%' if column == 1 => {
+                    self.take_while(|c| c != '\n');
+                    continue;
+                },
+                ';' => {
+                    self.bump();
+                    Token::Comment(self.take_while(|c| c != '\n').trim().to_string())
+                },
+                '{' => {
+                    self.bump();
+                    let comment = self.take_while(|c| c != '}');
+
+                    if self.bump().is_none() {
+                        return Err(self.error(line, column, PgnErrorKind::Unterminated));
+                    }
+
+                    // comments may be wrapped over more lines
+                    Token::Comment(comment.split_whitespace().collect::<Vec<_>>().join(" "))
+                },
+                '"' => {
+                    self.bump();
+                    let mut value = String::new();
+
+                    loop {
+                        match self.bump() {
+                            Some('"') => break,
+                            Some('\\') => match self.bump() {
+                                Some(c) => value.push(c),
+                                None => return Err(self.error(line, column, PgnErrorKind::Unterminated))
+                            },
+                            Some('\n') | None => return Err(self.error(line, column, PgnErrorKind::Unterminated)),
+                            Some(c) => value.push(c)
+                        }
+                    }
+
+                    Token::Str(value)
+                },
+                '[' | ']' | '(' | ')' | '*' => {
+                    self.bump();
+
+                    match c {
+                        '[' => Token::TagStart,
+                        ']' => Token::TagEnd,
+                        '(' => Token::VariationStart,
+                        ')' => Token::VariationEnd,
+                        _ => Token::Symbol("*".to_string())
+                    }
+                },
+                '$' => {
+                    self.bump();
+                    let digits = self.take_while(|c| c.is_ascii_digit());
+
+                    match digits.parse() {
+                        Ok(nag) => Token::Nag(nag),
+                        Err(_) => return Err(self.error(line, column, PgnErrorKind::InvalidNag(format!("${}", digits))))
+                    }
+                },
+                '!' | '?' => {
+                    let suffix = self.take_while(|c| c == '!' || c == '?');
+
+                    match suffix_nag(&suffix) {
+                        Some(nag) => Token::Nag(nag),
+                        None => return Err(self.error(line, column, PgnErrorKind::InvalidNag(suffix)))
+                    }
+                },
+                _ if c.is_ascii_digit() => {
+                    let digits = self.take_while(|c| c.is_ascii_digit());
+
+                    if self.chars.peek() == Some(&'.') {
+                        self.take_while(|c| c == '.');
+                        Token::MoveNumber
+                    } else {
+                        Token::Symbol(digits + &self.take_while(is_symbol_char))
+                    }
+                },
+                _ if is_symbol_char(c) => Token::Symbol(self.take_while(is_symbol_char)),
+                _ => return Err(self.error(line, column, PgnErrorKind::UnexpectedCharacter(c)))
+            };
+
+            return Ok(Some((token, line, column)));
+        }
+    }
+}
+
+fn is_result(symbol: &str) -> bool {
+    matches!(symbol, "1-0" | "0-1" | "1/2-1/2" | "*")
+}
+
+/// # Iterator over games of PGN text
+///
+/// every move is replayed on a board, so that only legal games are returned,
+/// reading stops at the first error
+///
+pub struct PgnReader<'a> {
+    lexer: Lexer<'a>,
+    failed: bool
+}
+
+impl<'a> PgnReader<'a> {
+    pub fn new(text: &'a str) -> PgnReader<'a> {
+        PgnReader { lexer: Lexer::new(text), failed: false }
+    }
+
+    fn read_game(&mut self) -> Result<Option<PgnGame>, PgnError> {
+        if self.lexer.peek()?.is_none() {
+            return Ok(None);
+        }
+
+        let mut tags = Vec::new();
+        let mut start = Board::new();
+
+        while let Some((Token::TagStart, ..)) = self.lexer.peek()? {
+            let (_, line, column) = self.lexer.next()?.unwrap();
+            let invalid = PgnError { line, column, kind: PgnErrorKind::InvalidTag };
+
+            let (name, value) = match (self.lexer.next()?, self.lexer.next()?, self.lexer.next()?) {
+                (Some((Token::Symbol(name), ..)), Some((Token::Str(value), ..)), Some((Token::TagEnd, ..))) => (name, value),
+                _ => return Err(invalid)
+            };
+
+            if name == "FEN" {
+                start = Board::from_fen(&value).map_err(|e| PgnError { line, column, kind: PgnErrorKind::Fen(e) })?;
+            }
+
+            tags.push((name, value));
+        }
+
+        if is_chess960(&tags) {
+            start.set_chess960(true);
+        }
+
+        let (moves, result) = self.read_line(&mut start, 0)?;
+
+        Ok(Some(PgnGame { tags, moves, result: result.unwrap_or_else(|| "*".to_string()) }))
+    }
+
+    /// # Reads moves until the end of game or variation, returns them with result ending the game
+    fn read_line(&mut self, board: &mut Board, depth: usize) -> Result<(Vec<PgnMove>, Option<String>), PgnError> {
+        let mut moves: Vec<PgnMove> = Vec::new();
+        let mut pending_comments = Vec::new();
+        // position before the last move, variations replace the last move
+        let mut previous = board.clone();
+
+        loop {
+            let (token, line, column) = match self.lexer.peek()? {
+                // tags of next game, when previous game has no result
+                Some((Token::TagStart, ..)) | None if depth == 0 => return Ok((moves, None)),
+                Some(_) => self.lexer.next()?.unwrap(),
+                None => return Err(PgnError { line: self.lexer.line, column: self.lexer.column, kind: PgnErrorKind::UnclosedVariation })
+            };
+
+            let error = |kind| PgnError { line, column, kind };
+
+            match token {
+                Token::MoveNumber => (),
+                Token::Symbol(symbol) if is_result(&symbol) => match depth {
+                    0 => return Ok((moves, Some(symbol))),
+                    _ => return Err(error(PgnErrorKind::ResultInVariation(symbol)))
+                },
+                Token::Symbol(san) => {
+                    let m = board.parse_san(&san).map_err(|e| error(PgnErrorKind::Move(e)))?;
+                    let mut pgn_move = PgnMove::new(m, san);
+
+                    pgn_move.comments_before = std::mem::take(&mut pending_comments);
+                    previous = board.clone();
+                    board.apply_move(m);
+                    moves.push(pgn_move);
+                },
+                Token::Comment(comment) => match moves.last_mut() {
+                    Some(last) => last.comments.push(comment),
+                    None => pending_comments.push(comment)
+                },
+                Token::Nag(nag) => match moves.last_mut() {
+                    Some(last) => last.nags.push(nag),
+                    None => return Err(error(PgnErrorKind::NoMove))
+                },
+                Token::VariationStart => {
+                    if moves.is_empty() {
+                        return Err(error(PgnErrorKind::NoMove));
+                    }
+
+                    let (variation, _) = self.read_line(&mut previous.clone(), depth + 1)?;
+                    moves.last_mut().unwrap().variations.push(variation);
+                },
+                Token::VariationEnd if depth > 0 => return Ok((moves, None)),
+                Token::VariationEnd => return Err(error(PgnErrorKind::UnexpectedVariationEnd)),
+                Token::TagStart | Token::TagEnd | Token::Str(_) => return Err(error(PgnErrorKind::UnexpectedCharacter(match token {
+                    Token::TagStart => '[',
+                    Token::TagEnd => ']',
+                    _ => '"'
+                })))
+            }
+        }
+    }
+}
+
+impl Iterator for PgnReader<'_> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let game = self.read_game();
+        self.failed = game.is_err();
+
+        game.transpose()
+    }
+}
@@ -1,4 +1,3 @@
-use crate::movement::Move;
 use std::ops::Not;
 
 
@@ -24,7 +23,37 @@ pub enum PieceType {
     Pawn, Rook, Knight, Bishop, Queen, King
 }
 
-#[derive(Debug)]
+impl PieceType {
+    /// # Piece types pawn can be promoted to
+    pub const PROMOTIONS: [PieceType; 4] = [PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight];
+
+    /// # Returns lowercase letter used for piece type in UCI notation
+    pub fn to_uci(&self) -> char {
+        match self {
+            PieceType::Pawn => 'p',
+            PieceType::Rook => 'r',
+            PieceType::Knight => 'n',
+            PieceType::Bishop => 'b',
+            PieceType::Queen => 'q',
+            PieceType::King => 'k'
+        }
+    }
+
+    /// # Returns piece type for lowercase letter used in UCI notation
+    pub fn from_uci(c: char) -> Option<PieceType> {
+        match c {
+            'p' => Some(PieceType::Pawn),
+            'r' => Some(PieceType::Rook),
+            'n' => Some(PieceType::Knight),
+            'b' => Some(PieceType::Bishop),
+            'q' => Some(PieceType::Queen),
+            'k' => Some(PieceType::King),
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Piece {
     piece_type: PieceType,
     piece_color: PieceColor,
@@ -43,53 +72,6 @@ impl Piece {
         }
     }
 
-    pub fn can_move_to(&self, m: Move, dest_occupied: bool) -> (bool, bool) {
-        let (dx, dy) = m.to_deltas();
-
-        match self.piece_type {
-            PieceType::Knight => ( (dx == 2 && dy == 1) || (dx == 1 && dy == 2), false ),
-            PieceType::Queen => ( dx == 0 || dy == 0 || dx == dy, true),
-            PieceType::King => ( dx <= 1 && dy <= 1, false ),
-            PieceType::Rook => ( dx == 0 || dy == 0, true ),
-            PieceType::Bishop => ( dx == dy, true ),
-            PieceType::Pawn => {
-                let ((sx, sy), (ex, ey)) = m.to_coords();
-
-                let distance = match self.piece_color {
-                    PieceColor::WHITE => {
-                        if ey > sy { 
-                            ey - sy
-                        } else {
-                            return (false, false);
-                        } 
-                    },
-                    PieceColor::BLACK => {
-                        if ey < sy {
-                            sy - ey
-                        } else {
-                            return (false, false);
-                        }
-                    }
-                };
-
-
-                if sx == ex && !dest_occupied {
-                    match distance {
-                        1 => (true, false),
-                        2 => (!self.moved, true),
-                        _ => (false, false)
-                    }
-                } else if dest_occupied && distance == 1 {
-                    if sx > ex {
-                        (sx - ex == 1, false) 
-                    } else if sx < ex {
-                        (ex - sx == 1, false) 
-                    } else { (false, false) }
-                } else { (false, false) } // todo en passant 
-            },
-        }
-    }
-
     pub fn color(&self) -> PieceColor {
         self.piece_color
     }
@@ -98,7 +80,57 @@ impl Piece {
         self.piece_type
     }
 
+    pub fn moved(&self) -> bool {
+        self.moved
+    }
+
     pub fn move_piece(&mut self) {
         self.moved = true;
     }    
 }
+
+/// # Material value of every piece type, in centipawns
+///
+/// ```
+/// use chess_api::piece::{PieceType, PieceValues};
+///
+/// let values = PieceValues { knight: 300, ..PieceValues::default() };
+///
+/// assert_eq!(values.value(PieceType::Knight), 300);
+/// assert_eq!(values.value(PieceType::Pawn), 100);
+/// ```
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct PieceValues {
+    pub pawn: i32,
+    pub knight: i32,
+    pub bishop: i32,
+    pub rook: i32,
+    pub queen: i32,
+    pub king: i32
+}
+
+impl PieceValues {
+    pub fn value(&self, piece_type: PieceType) -> i32 {
+        match piece_type {
+            PieceType::Pawn => self.pawn,
+            PieceType::Knight => self.knight,
+            PieceType::Bishop => self.bishop,
+            PieceType::Rook => self.rook,
+            PieceType::Queen => self.queen,
+            PieceType::King => self.king
+        }
+    }
+}
+
+impl Default for PieceValues {
+    fn default() -> Self {
+        PieceValues {
+            pawn: 100,
+            knight: 320,
+            bishop: 330,
+            rook: 500,
+            queen: 900,
+            king: 20000
+        }
+    }
+}
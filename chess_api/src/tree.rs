@@ -0,0 +1,413 @@
+use crate::board::{Board, MoveFailReason, PostMoveState};
+use crate::game::{Game, GameError};
+use crate::movement::Move;
+use crate::pgn::{PgnGame, PgnMove};
+
+
+/// # Identifier of node in game tree, stays valid until the node is deleted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// # Position in game tree with move leading to it
+#[derive(Debug, Clone)]
+pub struct Node {
+    m: Option<Move>,
+    san: String,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    /// comments before the move, used at start of a variation
+    pub comments_before: Vec<String>,
+    pub comments: Vec<String>,
+    pub nags: Vec<u8>
+}
+
+impl Node {
+    fn new(m: Option<Move>, san: String, parent: Option<NodeId>) -> Node {
+        Node {
+            m,
+            san,
+            parent,
+            children: Vec::new(),
+            comments_before: Vec::new(),
+            comments: Vec::new(),
+            nags: Vec::new()
+        }
+    }
+
+    /// # Returns move leading to this node, root has none
+    pub fn get_move(&self) -> Option<Move> {
+        self.m
+    }
+
+    /// # Returns SAN of move leading to this node, empty for root
+    pub fn san(&self) -> &str {
+        &self.san
+    }
+
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// # Returns following nodes, the first one continues mainline and others are variations
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+}
+
+/// # Game with variations
+///
+/// every node holds move leading to it, root holds starting position,
+/// board of current node is kept in sync when moving through the tree
+///
+/// ```
+/// # use chess_api::tree::GameTree;
+///
+/// let mut tree = GameTree::new();
+///
+/// let e4 = tree.play_san("e4").unwrap();
+/// tree.play_san("e5").unwrap();
+///
+/// // alternative to e5
+/// tree.go_to(e4);
+/// let c5 = tree.play_san("c5").unwrap();
+///
+/// assert_eq!(tree.mainline().iter().map(|id| tree.node(*id).san()).collect::<Vec<_>>(), ["e4", "e5"]);
+///
+/// tree.promote(c5);
+/// assert_eq!(tree.mainline().iter().map(|id| tree.node(*id).san()).collect::<Vec<_>>(), ["e4", "c5"]);
+/// assert_eq!(tree.board().to_fen(), "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2");
+/// ```
+#[derive(Clone)]
+pub struct GameTree {
+    start: Board,
+    /// deleted nodes leave empty slots, so that identifiers of others do not change
+    nodes: Vec<Option<Node>>,
+    current: NodeId,
+    board: Board
+}
+
+impl GameTree {
+    /// # Creates tree with standard starting position
+    pub fn new() -> GameTree {
+        GameTree::from_board(Board::new())
+    }
+
+    /// # Creates tree starting at given position
+    pub fn from_board(board: Board) -> GameTree {
+        GameTree {
+            start: board.clone(),
+            nodes: vec![Some(Node::new(None, String::new(), None))],
+            current: NodeId(0),
+            board
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    /// # Returns node the board is at
+    pub fn current(&self) -> NodeId {
+        self.current
+    }
+
+    /// # Returns position of current node
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// # Returns position the tree starts from
+    pub fn start_board(&self) -> &Board {
+        &self.start
+    }
+
+    /// # Returns node with given identifier
+    ///
+    /// panics when the node was deleted
+    ///
+    pub fn node(&self, id: NodeId) -> &Node {
+        self.nodes[id.0].as_ref().expect("node was deleted")
+    }
+
+    /// # Returns node for changing its comments and NAGs
+    pub fn node_mut(&mut self, id: NodeId) -> &mut Node {
+        self.nodes[id.0].as_mut().expect("node was deleted")
+    }
+
+    /// # Returns true if node with given identifier was not deleted
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.nodes.get(id.0).is_some_and(Option::is_some)
+    }
+
+    /// # Plays move from current node and moves to its node
+    ///
+    /// move which already follows current node is not added again,
+    /// new move continues mainline when current node has no children, otherwise it starts variation
+    ///
+    pub fn play(&mut self, m: Move) -> Result<NodeId, MoveFailReason> {
+        if let Some(child) = self.node(self.current).children.iter().find(|child| self.node(**child).m == Some(m)) {
+            let child = *child;
+            self.go_to(child);
+            return Ok(child);
+        }
+
+        let san = self.board.to_san(m);
+        self.board.perform_move(m)?;
+
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Some(Node::new(Some(m), san, Some(self.current))));
+        self.node_mut(self.current).children.push(id);
+        self.current = id;
+
+        Ok(id)
+    }
+
+    /// # Plays move written in SAN, see `play`
+    pub fn play_san(&mut self, san: &str) -> Result<NodeId, GameError> {
+        let m = self.board.parse_san(san).map_err(GameError::San)?;
+        self.play(m).map_err(GameError::Move)
+    }
+
+    /// # Adds variation of moves written in SAN after given node, returns node of its last move
+    ///
+    /// board stays at current node, nothing is added when any move is not legal
+    ///
+    pub fn add_variation(&mut self, parent: NodeId, moves: &[&str]) -> Result<NodeId, GameError> {
+        let current = self.current;
+        let added = self.nodes.len();
+        let mut last = parent;
+
+        self.go_to(parent);
+
+        for san in moves {
+            match self.play_san(san) {
+                Ok(id) => last = id,
+                Err(e) => {
+                    // only nodes created by this call are removed
+                    let first_new = self.path(last).into_iter().find(|id| id.0 >= added);
+
+                    if let Some(first_new) = first_new {
+                        self.delete(first_new);
+                    }
+
+                    self.go_to(current);
+                    return Err(e);
+                }
+            }
+        }
+
+        self.go_to(current);
+        Ok(last)
+    }
+
+    /// # Moves board to given node
+    pub fn go_to(&mut self, id: NodeId) {
+        let mut board = self.start.clone();
+
+        for node in self.path(id) {
+            // moves were checked when they were added
+            board.apply_move(self.node(node).m.unwrap());
+        }
+
+        self.board = board;
+        self.current = id;
+    }
+
+    /// # Moves board one move back, returns false at root
+    pub fn go_back(&mut self) -> bool {
+        match self.node(self.current).parent {
+            Some(parent) => {
+                self.go_to(parent);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// # Moves board one move forward along mainline of current node, returns false at its end
+    pub fn go_forward(&mut self) -> bool {
+        match self.node(self.current).children.first() {
+            Some(child) => {
+                let child = *child;
+                self.board.apply_move(self.node(child).m.unwrap());
+                self.current = child;
+                true
+            },
+            None => false
+        }
+    }
+
+    /// # Returns nodes from the first move to given node
+    pub fn path(&self, id: NodeId) -> Vec<NodeId> {
+        let mut path = Vec::new();
+        let mut node = id;
+
+        while let Some(parent) = self.node(node).parent {
+            path.push(node);
+            node = parent;
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// # Returns nodes of mainline, without root
+    pub fn mainline(&self) -> Vec<NodeId> {
+        let mut mainline = Vec::new();
+        let mut node = self.root();
+
+        while let Some(child) = self.node(node).children.first() {
+            mainline.push(*child);
+            node = *child;
+        }
+
+        mainline
+    }
+
+    /// # Makes line leading to given node mainline of the whole tree
+    pub fn promote(&mut self, id: NodeId) {
+        for node in self.path(id) {
+            self.promote_variation(node);
+        }
+    }
+
+    /// # Makes given node first child of its parent, only this variation is promoted
+    pub fn promote_variation(&mut self, id: NodeId) {
+        if let Some(parent) = self.node(id).parent {
+            let children = &mut self.node_mut(parent).children;
+            let index = children.iter().position(|child| *child == id).unwrap();
+
+            children[..=index].rotate_right(1);
+        }
+    }
+
+    /// # Deletes given node with all following moves
+    ///
+    /// board moves to parent when current node is deleted, root can not be deleted
+    ///
+    pub fn delete(&mut self, id: NodeId) {
+        let parent = match self.node(id).parent {
+            Some(parent) => parent,
+            None => return
+        };
+
+        if self.path(self.current).contains(&id) {
+            self.go_to(parent);
+        }
+
+        self.node_mut(parent).children.retain(|child| *child != id);
+
+        let mut removed = vec![id];
+
+        while let Some(node) = removed.pop() {
+            removed.extend(self.nodes[node.0].take().unwrap().children);
+        }
+    }
+
+    /// # Returns game with moves leading to current node
+    pub fn to_game(&self) -> Game {
+        let mut game = Game::from_board(self.start.clone());
+
+        for node in self.path(self.current) {
+            game.play(self.node(node).m.unwrap()).unwrap();
+        }
+
+        game
+    }
+
+    /// # Returns state of the game after move of current node
+    pub fn state(&self) -> PostMoveState {
+        self.to_game().last_move().map(|m| m.state()).unwrap_or(PostMoveState::Normal)
+    }
+
+    /// # Returns moves following given node as PGN line, variations are kept
+    fn pgn_line(&self, parent: NodeId) -> Vec<PgnMove> {
+        let mut line = Vec::new();
+        let mut node = parent;
+
+        while let Some((main, variations)) = self.node(node).children.split_first() {
+            let mut pgn_move = self.pgn_move(*main);
+            pgn_move.variations = variations.iter().map(|variation| {
+                let mut moves = vec![self.pgn_move(*variation)];
+                moves.extend(self.pgn_line(*variation));
+                moves
+            }).collect();
+
+            line.push(pgn_move);
+            node = *main;
+        }
+
+        line
+    }
+
+    fn pgn_move(&self, id: NodeId) -> PgnMove {
+        let node = self.node(id);
+
+        PgnMove {
+            m: node.m.unwrap(),
+            san: node.san.clone(),
+            nags: node.nags.clone(),
+            comments_before: node.comments_before.clone(),
+            comments: node.comments.clone(),
+            variations: Vec::new()
+        }
+    }
+
+    /// # Adds PGN line with its variations after given node
+    fn add_pgn_line(&mut self, parent: NodeId, line: &[PgnMove]) {
+        let mut node = parent;
+
+        for pgn_move in line {
+            self.go_to(node);
+            // moves of PGN game were checked while reading
+            let added = self.play(pgn_move.m).unwrap();
+
+            let added_node = self.node_mut(added);
+            added_node.comments_before.extend(pgn_move.comments_before.iter().cloned());
+            added_node.comments.extend(pgn_move.comments.iter().cloned());
+            added_node.nags.extend(pgn_move.nags.iter().copied());
+
+            // variations are added after the move, so that it stays the first child
+            for variation in &pgn_move.variations {
+                self.add_pgn_line(node, variation);
+            }
+
+            node = added;
+        }
+    }
+
+    /// # Returns PGN game with the whole tree, tags are taken from given game
+    ///
+    /// ```
+    /// # use chess_api::pgn::PgnGame;
+    /// # use chess_api::tree::GameTree;
+    ///
+    /// let pgn: PgnGame = "[Event \"x\"]\n\n1. e4 e5 (1... c5 $1 {Sicilian}) 2. Nf3 *".parse().unwrap();
+    /// let tree = GameTree::from(&pgn);
+    ///
+    /// assert_eq!(tree.to_pgn(&pgn), pgn);
+    /// ```
+    pub fn to_pgn(&self, tags: &PgnGame) -> PgnGame {
+        let mut pgn = tags.clone();
+        pgn.moves = self.pgn_line(self.root());
+        pgn
+    }
+}
+
+impl From<&PgnGame> for GameTree {
+    /// # Creates tree with every move and variation of PGN game, board is at root
+    fn from(pgn: &PgnGame) -> Self {
+        let mut tree = GameTree::from_board(pgn.start_board());
+        let root = tree.root();
+
+        tree.add_pgn_line(root, &pgn.moves);
+        tree.go_to(root);
+        tree
+    }
+}
+
+impl Default for GameTree {
+    fn default() -> Self {
+        GameTree::new()
+    }
+}
@@ -0,0 +1,518 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::engine::{BestMove, SearchLimits, MATE};
+use crate::movement::{Move, UciError};
+use crate::piece::PieceColor;
+
+
+/// # Reason why UCI command could not be parsed
+#[derive(Debug, PartialEq)]
+pub enum CommandError {
+    Empty,
+    UnknownCommand(String),
+    MissingValue(String),
+    InvalidValue(String),
+    InvalidMove(UciError)
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::Empty => write!(f, "empty command"),
+            CommandError::UnknownCommand(s) => write!(f, "unknown command '{}'", s),
+            CommandError::MissingValue(s) => write!(f, "missing value of '{}'", s),
+            CommandError::InvalidValue(s) => write!(f, "'{}' is not a valid value", s),
+            CommandError::InvalidMove(e) => write!(f, "{}", e)
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// # Parameters of `go` command
+///
+/// times are given in milliseconds by the protocol
+///
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct GoOptions {
+    pub white_time: Option<Duration>,
+    pub black_time: Option<Duration>,
+    pub white_increment: Option<Duration>,
+    pub black_increment: Option<Duration>,
+    pub moves_to_go: Option<u32>,
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+    pub mate: Option<u32>,
+    pub move_time: Option<Duration>,
+    pub infinite: bool,
+    pub ponder: bool
+}
+
+impl GoOptions {
+    /// # Returns search limits for given side to move
+    ///
+    /// infinite search and pondering have no limits, they end with `stop`
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use chess_api::piece::PieceColor;
+    /// use chess_api::uci::{Command, GoOptions};
+    ///
+    /// let go = match "go wtime 60000 btime 1000 movestogo 10".parse() {
+    ///     Ok(Command::Go(go)) => go,
+    ///     _ => unreachable!()
+    /// };
+    ///
+    /// assert_eq!(go.limits(PieceColor::WHITE).time, Some(Duration::from_secs(6)));
+    /// assert_eq!(go.limits(PieceColor::BLACK).time, Some(Duration::from_millis(100)));
+    /// ```
+    pub fn limits(&self, side: PieceColor) -> SearchLimits {
+        if self.infinite || self.ponder {
+            return SearchLimits::default();
+        }
+
+        let (time, increment) = match side {
+            PieceColor::WHITE => (self.white_time, self.white_increment),
+            PieceColor::BLACK => (self.black_time, self.black_increment)
+        };
+
+        let clock = time.and_then(|time| SearchLimits::from_clock(time, increment.unwrap_or(Duration::ZERO), self.moves_to_go).time);
+
+        SearchLimits {
+            // mate in n moves is found within 2n - 1 halfmoves
+            depth: self.depth.or(self.mate.map(|moves| (moves * 2).saturating_sub(1).max(1))),
+            nodes: self.nodes,
+            time: self.move_time.or(clock)
+        }
+    }
+}
+
+impl fmt::Display for GoOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "go")?;
+
+        let times = [
+            ("wtime", self.white_time),
+            ("btime", self.black_time),
+            ("winc", self.white_increment),
+            ("binc", self.black_increment),
+            ("movetime", self.move_time)
+        ];
+
+        for (name, time) in times {
+            if let Some(time) = time {
+                write!(f, " {} {}", name, time.as_millis())?;
+            }
+        }
+
+        if let Some(moves) = self.moves_to_go {
+            write!(f, " movestogo {}", moves)?;
+        }
+
+        if let Some(depth) = self.depth {
+            write!(f, " depth {}", depth)?;
+        }
+
+        if let Some(nodes) = self.nodes {
+            write!(f, " nodes {}", nodes)?;
+        }
+
+        if let Some(mate) = self.mate {
+            write!(f, " mate {}", mate)?;
+        }
+
+        if self.infinite {
+            write!(f, " infinite")?;
+        }
+
+        if self.ponder {
+            write!(f, " ponder")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<SearchLimits> for GoOptions {
+    fn from(limits: SearchLimits) -> Self {
+        GoOptions {
+            depth: limits.depth,
+            nodes: limits.nodes,
+            move_time: limits.time,
+            ..GoOptions::default()
+        }
+    }
+}
+
+/// # Command sent from GUI to engine
+///
+/// ```
+/// use chess_api::uci::Command;
+///
+/// let command: Command = "position startpos moves e2e4 e7e5".parse().unwrap();
+///
+/// match command {
+///     Command::Position { fen, moves } => {
+///         assert_eq!(fen, None);
+///         assert_eq!(moves.len(), 2);
+///     },
+///     _ => unreachable!()
+/// }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub enum Command {
+    Uci,
+    Debug(bool),
+    IsReady,
+    SetOption { name: String, value: Option<String> },
+    UciNewGame,
+    /// position given by FEN, None for starting position, followed by moves
+    Position { fen: Option<String>, moves: Vec<Move> },
+    Go(GoOptions),
+    Stop,
+    PonderHit,
+    Quit
+}
+
+impl FromStr for Command {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+
+        let (&command, args) = match tokens.split_first() {
+            Some(split) => split,
+            None => return Err(CommandError::Empty)
+        };
+
+        match command {
+            "uci" => Ok(Command::Uci),
+            "debug" => Ok(Command::Debug(args.first() != Some(&"off"))),
+            "isready" => Ok(Command::IsReady),
+            "setoption" => parse_set_option(args),
+            "ucinewgame" => Ok(Command::UciNewGame),
+            "position" => parse_position(args),
+            "go" => parse_go(args).map(Command::Go),
+            "stop" => Ok(Command::Stop),
+            "ponderhit" => Ok(Command::PonderHit),
+            "quit" => Ok(Command::Quit),
+            _ => Err(CommandError::UnknownCommand(command.to_string()))
+        }
+    }
+}
+
+/// # Parses `name <name> [value <value>]`, both name and value can contain spaces
+fn parse_set_option(args: &[&str]) -> Result<Command, CommandError> {
+    if args.first() != Some(&"name") {
+        return Err(CommandError::MissingValue("name".to_string()));
+    }
+
+    let value_index = args.iter().position(|arg| *arg == "value");
+    let name = args[1..value_index.unwrap_or(args.len())].join(" ");
+
+    if name.is_empty() {
+        return Err(CommandError::MissingValue("name".to_string()));
+    }
+
+    Ok(Command::SetOption {
+        name,
+        value: value_index.map(|i| args[i + 1..].join(" "))
+    })
+}
+
+fn parse_position(args: &[&str]) -> Result<Command, CommandError> {
+    let moves_index = args.iter().position(|arg| *arg == "moves").unwrap_or(args.len());
+
+    let fen = match args.first() {
+        Some(&"startpos") => None,
+        Some(&"fen") if moves_index > 1 => Some(args[1..moves_index].join(" ")),
+        Some(&"fen") => return Err(CommandError::MissingValue("fen".to_string())),
+        Some(other) => return Err(CommandError::InvalidValue(other.to_string())),
+        None => return Err(CommandError::MissingValue("position".to_string()))
+    };
+
+    let moves = args.get(moves_index + 1..).unwrap_or(&[]).iter()
+        .map(|m| m.parse().map_err(CommandError::InvalidMove))
+        .collect::<Result<Vec<Move>, CommandError>>()?;
+
+    Ok(Command::Position { fen, moves })
+}
+
+fn parse_go(args: &[&str]) -> Result<GoOptions, CommandError> {
+    let mut go = GoOptions::default();
+    let mut args = args.iter();
+
+    fn value<T: FromStr>(name: &str, arg: Option<&&str>) -> Result<T, CommandError> {
+        match arg {
+            Some(arg) => arg.parse().map_err(|_| CommandError::InvalidValue(arg.to_string())),
+            None => Err(CommandError::MissingValue(name.to_string()))
+        }
+    }
+
+    // clocks can be negative when GUI lets engine run out of time
+    let millis = |name: &str, arg: Option<&&str>| value::<i64>(name, arg).map(|ms| Duration::from_millis(ms.max(0) as u64));
+
+    while let Some(&arg) = args.next() {
+        match arg {
+            "wtime" => go.white_time = Some(millis(arg, args.next())?),
+            "btime" => go.black_time = Some(millis(arg, args.next())?),
+            "winc" => go.white_increment = Some(millis(arg, args.next())?),
+            "binc" => go.black_increment = Some(millis(arg, args.next())?),
+            "movestogo" => go.moves_to_go = Some(value(arg, args.next())?),
+            "depth" => go.depth = Some(value(arg, args.next())?),
+            "nodes" => go.nodes = Some(value(arg, args.next())?),
+            "mate" => go.mate = Some(value(arg, args.next())?),
+            "movetime" => go.move_time = Some(millis(arg, args.next())?),
+            "infinite" => go.infinite = true,
+            "ponder" => go.ponder = true,
+            // restricting search to given moves is not supported, moves are skipped
+            "searchmoves" => {
+                while args.as_slice().first().is_some_and(|arg| arg.parse::<Move>().is_ok()) {
+                    args.next();
+                }
+            },
+            _ => return Err(CommandError::InvalidValue(arg.to_string()))
+        }
+    }
+
+    Ok(go)
+}
+
+/// # Score reported by engine
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Score {
+    /// centipawns from side to move's point of view
+    Centipawns(i32),
+    /// moves to mate, negative when side to move gets mated
+    Mate(i32)
+}
+
+/// # Search progress sent from engine to GUI in `info` line
+///
+/// ```
+/// use std::time::Duration;
+/// use chess_api::uci::{Info, Score};
+///
+/// let info = Info {
+///     depth: Some(2),
+///     score: Some(Score::Centipawns(35)),
+///     nodes: Some(120),
+///     time: Some(Duration::from_millis(4)),
+///     pv: vec!["e2e4".parse().unwrap(), "e7e5".parse().unwrap()],
+///     ..Info::default()
+/// };
+///
+/// assert_eq!(info.to_string(), "info depth 2 score cp 35 nodes 120 nps 30000 time 4 pv e2e4 e7e5");
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Info {
+    pub depth: Option<u32>,
+    pub score: Option<Score>,
+    pub nodes: Option<u64>,
+    pub time: Option<Duration>,
+    pub pv: Vec<Move>,
+    pub string: Option<String>
+}
+
+impl From<&BestMove> for Info {
+    fn from(best: &BestMove) -> Self {
+        let score = match best.mate_in() {
+            Some(moves) => Score::Mate(moves),
+            None => Score::Centipawns(best.score().clamp(-MATE, MATE))
+        };
+
+        Info {
+            depth: Some(best.depth()),
+            score: Some(score),
+            nodes: Some(best.nodes()),
+            time: Some(best.time()),
+            pv: best.pv().to_vec(),
+            string: None
+        }
+    }
+}
+
+impl fmt::Display for Info {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "info")?;
+
+        if let Some(depth) = self.depth {
+            write!(f, " depth {}", depth)?;
+        }
+
+        match self.score {
+            Some(Score::Centipawns(cp)) => write!(f, " score cp {}", cp)?,
+            Some(Score::Mate(moves)) => write!(f, " score mate {}", moves)?,
+            None => ()
+        }
+
+        if let Some(nodes) = self.nodes {
+            write!(f, " nodes {}", nodes)?;
+
+            if let Some(time) = self.time {
+                write!(f, " nps {}", (nodes as f64 / time.as_secs_f64().max(0.001)) as u64)?;
+            }
+        }
+
+        if let Some(time) = self.time {
+            write!(f, " time {}", time.as_millis())?;
+        }
+
+        if !self.pv.is_empty() {
+            write!(f, " pv")?;
+
+            for m in self.pv.iter() {
+                write!(f, " {}", m)?;
+            }
+        }
+
+        // string takes the rest of the line, so it goes last
+        if let Some(string) = &self.string {
+            write!(f, " string {}", string)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Info {
+    type Err = CommandError;
+
+    /// # Parses `info` line, values which are not kept in `Info` are skipped
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+
+        match tokens.first() {
+            Some(&"info") => (),
+            Some(other) => return Err(CommandError::UnknownCommand(other.to_string())),
+            None => return Err(CommandError::Empty)
+        }
+
+        let mut info = Info::default();
+        let mut i = 1;
+
+        fn value<T: FromStr>(tokens: &[&str], i: usize) -> Result<T, CommandError> {
+            match tokens.get(i + 1) {
+                Some(token) => token.parse().map_err(|_| CommandError::InvalidValue(token.to_string())),
+                None => Err(CommandError::MissingValue(tokens[i].to_string()))
+            }
+        }
+
+        while i < tokens.len() {
+            match tokens[i] {
+                "depth" => info.depth = Some(value(&tokens, i)?),
+                "nodes" => info.nodes = Some(value(&tokens, i)?),
+                "time" => info.time = Some(Duration::from_millis(value(&tokens, i)?)),
+                "score" => {
+                    info.score = Some(match tokens.get(i + 1) {
+                        Some(&"cp") => Score::Centipawns(value(&tokens, i + 1)?),
+                        Some(&"mate") => Score::Mate(value(&tokens, i + 1)?),
+                        _ => return Err(CommandError::MissingValue("score".to_string()))
+                    });
+
+                    i += 1;
+
+                    // bound of score found in partially searched iteration
+                    if matches!(tokens.get(i + 2), Some(&"lowerbound") | Some(&"upperbound")) {
+                        i += 1;
+                    }
+                },
+                "pv" => {
+                    // moves take the rest of the line up to next keyword
+                    while let Some(Ok(m)) = tokens.get(i + 1).map(|token| token.parse::<Move>()) {
+                        info.pv.push(m);
+                        i += 1;
+                    }
+
+                    i += 1;
+                    continue;
+                },
+                "refutation" | "currline" => {
+                    // lines of moves which are not kept, currline can start with cpu number
+                    while tokens.get(i + 1).is_some_and(|token| token.parse::<Move>().is_ok() || token.parse::<u32>().is_ok()) {
+                        i += 1;
+                    }
+
+                    i += 1;
+                    continue;
+                },
+                "string" => {
+                    info.string = Some(tokens[i + 1..].join(" "));
+                    break;
+                },
+                _ => ()
+            }
+
+            // every other keyword has single value
+            i += 2;
+        }
+
+        Ok(info)
+    }
+}
+
+/// # Message sent from engine to GUI
+///
+/// ```
+/// use chess_api::uci::EngineMessage;
+///
+/// let message: EngineMessage = "bestmove e2e4 ponder e7e5".parse().unwrap();
+///
+/// assert_eq!(message, EngineMessage::BestMove {
+///     m: Some("e2e4".parse().unwrap()),
+///     ponder: Some("e7e5".parse().unwrap())
+/// });
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub enum EngineMessage {
+    /// `id name` or `id author` followed by its value
+    Id { name: String, value: String },
+    UciOk,
+    ReadyOk,
+    /// best move is None when there are no legal moves
+    BestMove { m: Option<Move>, ponder: Option<Move> },
+    Info(Info),
+    /// description of option which can be changed with `setoption`
+    Option(String)
+}
+
+impl FromStr for EngineMessage {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+
+        let (&message, args) = match tokens.split_first() {
+            Some(split) => split,
+            None => return Err(CommandError::Empty)
+        };
+
+        match message {
+            "id" => match args.split_first() {
+                Some((name, value)) => Ok(EngineMessage::Id { name: name.to_string(), value: value.join(" ") }),
+                None => Err(CommandError::MissingValue("id".to_string()))
+            },
+            "uciok" => Ok(EngineMessage::UciOk),
+            "readyok" => Ok(EngineMessage::ReadyOk),
+            "bestmove" => {
+                let m = match args.first() {
+                    Some(&"0000") | Some(&"(none)") => None,
+                    Some(m) => Some(m.parse().map_err(CommandError::InvalidMove)?),
+                    None => return Err(CommandError::MissingValue("bestmove".to_string()))
+                };
+
+                let ponder = match args.get(1..3) {
+                    Some(["ponder", m]) => Some(m.parse().map_err(CommandError::InvalidMove)?),
+                    _ => None
+                };
+
+                Ok(EngineMessage::BestMove { m, ponder })
+            },
+            "info" => s.parse().map(EngineMessage::Info),
+            "option" => Ok(EngineMessage::Option(args.join(" "))),
+            _ => Err(CommandError::UnknownCommand(message.to_string()))
+        }
+    }
+}
@@ -0,0 +1,87 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use chess_api::board::*;
+use chess_api::movement::*;
+use chess_api::piece::*;
+
+fn squares(uci: &[&str]) -> Vec<Square> {
+    let mut squares: Vec<Square> = uci.iter().map(|s| s.parse().unwrap()).collect();
+    squares.sort_by_key(|square| square.to_index());
+    squares
+}
+
+    #[test]
+    fn checkers_match_king_attacked() {
+        let board = Board::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3").unwrap();
+
+        assert_eq!(board.checkers(PieceColor::WHITE).squares().collect::<Vec<_>>(), squares(&["h4"]));
+        assert_eq!(board.is_king_attacked(PieceColor::WHITE), true);
+        assert!(board.checkers(PieceColor::BLACK).is_empty());
+    }
+
+    #[test]
+    fn no_king_no_checkers_or_pins() {
+        let mut board = Board::new_clear();
+
+        board.set(Square::new(0, 0), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
+        board.set(Square::new(0, 4), Some(Piece::new(PieceType::Knight, PieceColor::WHITE)));
+
+        assert!(board.checkers(PieceColor::WHITE).is_empty());
+        assert!(board.pins(PieceColor::WHITE).is_empty());
+    }
+
+    #[test]
+    fn pins_on_lines_and_diagonals() {
+        // bishop on d2 pinned by queen on a5, pawn on e2 pinned by rook on e8, knight on g3 free
+        let board = Board::from_fen("4r2k/8/8/q7/8/6N1/3BP3/4K2r w - - 0 1").unwrap();
+        let mut pins = board.pins(PieceColor::WHITE);
+        pins.sort_by_key(|pin| pin.pinned().to_index());
+
+        assert_eq!(pins.len(), 2);
+
+        assert_eq!(pins[0].pinned(), "d2".parse().unwrap());
+        assert_eq!(pins[0].pinner(), "a5".parse().unwrap());
+        assert_eq!(pins[0].ray().squares().collect::<Vec<_>>(), squares(&["d2", "c3", "b4", "a5"]));
+
+        assert_eq!(pins[1].pinned(), "e2".parse().unwrap());
+        assert_eq!(pins[1].pinner(), "e8".parse().unwrap());
+        assert_eq!(pins[1].ray().count(), 7);
+
+        // rook on h1 gives check, it is not a pin
+        assert_eq!(board.checkers(PieceColor::WHITE).squares().collect::<Vec<_>>(), squares(&["h1"]));
+    }
+
+    #[test]
+    fn enemy_piece_on_ray_is_not_pinned() {
+        let board = Board::from_fen("4r2k/8/8/8/4n3/8/8/4K3 w - - 0 1").unwrap();
+
+        assert!(board.pins(PieceColor::WHITE).is_empty());
+        assert!(board.pins(PieceColor::BLACK).is_empty());
+    }
+
+    #[test]
+    fn attacked_squares_stop_at_blockers() {
+        let board = Board::from_fen("7k/8/8/8/3p4/8/8/R2K4 w - - 0 1").unwrap();
+        let attacked = board.attacked_squares(PieceColor::WHITE);
+
+        // rook covers whole a file and b1, c1, d1, king covers its neighbourhood
+        assert_eq!(attacked.count(), 7 + 3 + 4);
+        assert!(attacked.contains("d1".parse().unwrap()));
+        assert!(attacked.contains("e1".parse().unwrap()));
+
+        let attacked = board.attacked_squares(PieceColor::BLACK);
+        assert!(attacked.contains("c3".parse().unwrap()));
+        assert!(attacked.contains("e3".parse().unwrap()));
+        assert!(!attacked.contains("d3".parse().unwrap()));
+    }
+
+    #[test]
+    fn attackers_and_defenders() {
+        // white knight on e5 attacked by pawn d6 and rook e8, defended by pawn d4 and queen e1
+        let board = Board::from_fen("4r2k/8/3p4/4N3/3P4/8/8/4Q1K1 w - - 0 1").unwrap();
+        let e5 = "e5".parse().unwrap();
+
+        assert_eq!(board.attackers(e5, PieceColor::BLACK).squares().collect::<Vec<_>>(), squares(&["d6", "e8"]));
+        assert_eq!(board.defenders(e5).squares().collect::<Vec<_>>(), squares(&["d4", "e1"]));
+        assert_eq!(board.attackers(e5, PieceColor::WHITE), board.defenders(e5));
+    }
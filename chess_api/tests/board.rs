@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use chess_api::board::*;
 use chess_api::movement::*;
 use chess_api::piece::*;
@@ -88,6 +90,7 @@ use chess_api::piece::*;
         board.set(Square::new(1, 1), Some(Piece::new(PieceType::King, PieceColor::BLACK)));
         board.set(Square::new(1, 2), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
         board.set(Square::new(1, 6), Some(Piece::new(PieceType::Rook, PieceColor::WHITE)));
+        board.set_side_to_move(PieceColor::BLACK);
 
         assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 2), Square::new(6, 2))).is_ok(), false);
         assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 2), Square::new(1, 3))).is_ok(), true);
@@ -151,6 +154,7 @@ use chess_api::piece::*;
 
         board.set(Square::new(1, 1), Some(Piece::new(PieceType::King, PieceColor::WHITE)));
         board.set(Square::new(5, 5), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
+        board.set_side_to_move(PieceColor::BLACK);
 
         assert_eq!(board.perform_move(Move::new(Square::new(5, 5), Square::new(5, 1))).ok().unwrap(), PostMoveState::Check);
     }
@@ -162,6 +166,7 @@ use chess_api::piece::*;
         board.set(Square::new(0, 1), Some(Piece::new(PieceType::King, PieceColor::WHITE)));
         board.set(Square::new(2, 1), Some(Piece::new(PieceType::Knight, PieceColor::BLACK)));
         board.set(Square::new(1, 7), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
+        board.set_side_to_move(PieceColor::BLACK);
 
         assert_eq!(board.perform_move(Move::new(Square::new(1, 7), Square::new(1, 6))).ok().unwrap(), PostMoveState::Stelemate);
     }
@@ -176,3 +181,248 @@ use chess_api::piece::*;
 
         assert_eq!(board.perform_move(Move::new(Square::new(7, 1), Square::new(7, 0))).ok().unwrap(), PostMoveState::Checkmate);
     }
+
+    fn castling_board() -> Board {
+        let mut board = Board::new_clear();
+
+        board.set(Square::new(4, 0), Some(Piece::new(PieceType::King, PieceColor::WHITE)));
+        board.set(Square::new(0, 0), Some(Piece::new(PieceType::Rook, PieceColor::WHITE)));
+        board.set(Square::new(7, 0), Some(Piece::new(PieceType::Rook, PieceColor::WHITE)));
+        board.set(Square::new(4, 7), Some(Piece::new(PieceType::King, PieceColor::BLACK)));
+        board.set(Square::new(0, 7), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
+        board.set(Square::new(7, 7), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
+
+        board
+    }
+
+    #[test]
+    fn castling_king_side() {
+        let mut board = castling_board();
+
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 0), Square::new(6, 0))).is_ok(), true);
+        assert_eq!(board.get_piece(Square::new(6, 0)).unwrap().piece_type(), PieceType::King);
+        assert_eq!(board.get_piece(Square::new(5, 0)).unwrap().piece_type(), PieceType::Rook);
+        assert!(board.get_piece(Square::new(4, 0)).is_none());
+        assert!(board.get_piece(Square::new(7, 0)).is_none());
+
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 7), Square::new(2, 7))).is_ok(), true);
+        assert_eq!(board.get_piece(Square::new(2, 7)).unwrap().piece_type(), PieceType::King);
+        assert_eq!(board.get_piece(Square::new(3, 7)).unwrap().piece_type(), PieceType::Rook);
+    }
+
+    #[test]
+    fn castling_queen_side() {
+        let mut board = castling_board();
+
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 0), Square::new(2, 0))).is_ok(), true);
+        assert_eq!(board.get_piece(Square::new(2, 0)).unwrap().piece_type(), PieceType::King);
+        assert_eq!(board.get_piece(Square::new(3, 0)).unwrap().piece_type(), PieceType::Rook);
+        assert!(board.get_piece(Square::new(0, 0)).is_none());
+
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 7), Square::new(6, 7))).is_ok(), true);
+        assert_eq!(board.get_piece(Square::new(6, 7)).unwrap().piece_type(), PieceType::King);
+        assert_eq!(board.get_piece(Square::new(5, 7)).unwrap().piece_type(), PieceType::Rook);
+    }
+
+    #[test]
+    fn castling_blocked() {
+        let mut board = castling_board();
+
+        board.set(Square::new(1, 0), Some(Piece::new(PieceType::Knight, PieceColor::WHITE)));
+        board.set(Square::new(6, 0), Some(Piece::new(PieceType::Knight, PieceColor::BLACK)));
+
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(2, 0))).err().unwrap(), MoveFailReason::IllegalMove);
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(6, 0))).err().unwrap(), MoveFailReason::IllegalMove);
+    }
+
+    #[test]
+    fn castling_through_attacked_squares() {
+        let mut board = castling_board();
+
+        // rook on f8 attacks f1, pawn on b2 attacks c1
+        board.set(Square::new(5, 7), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
+        board.set(Square::new(1, 1), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
+
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(6, 0))).err().unwrap(), MoveFailReason::KingAttacked);
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(2, 0))).err().unwrap(), MoveFailReason::KingAttacked);
+
+        // rook on e8 gives check
+        let mut board = castling_board();
+
+        board.set(Square::new(4, 7), None);
+        board.set(Square::new(4, 5), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
+        board.set(Square::new(0, 7), Some(Piece::new(PieceType::King, PieceColor::BLACK)));
+
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(6, 0))).err().unwrap(), MoveFailReason::KingAttacked);
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(2, 0))).err().unwrap(), MoveFailReason::KingAttacked);
+    }
+
+    #[test]
+    fn castling_attacked_rook_is_allowed() {
+        let mut board = castling_board();
+
+        // b1 and a1 may be attacked during queen side castling
+        board.set(Square::new(1, 5), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
+
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(2, 0))).is_ok(), true);
+    }
+
+    #[test]
+    fn castling_after_king_or_rook_moved() {
+        let mut board = castling_board();
+
+        assert_eq!(board.perform_move(Move::new(Square::new(7, 0), Square::new(7, 1))).is_ok(), true);
+        assert_eq!(board.perform_move(Move::new(Square::new(0, 7), Square::new(0, 6))).is_ok(), true);
+        assert_eq!(board.perform_move(Move::new(Square::new(7, 1), Square::new(7, 0))).is_ok(), true);
+        assert_eq!(board.perform_move(Move::new(Square::new(0, 6), Square::new(0, 7))).is_ok(), true);
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(6, 0))).is_ok(), false);
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(2, 0))).is_ok(), true);
+
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 0), Square::new(3, 0))).is_ok(), true);
+        assert_eq!(board.perform_move(Move::new(Square::new(0, 7), Square::new(0, 6))).is_ok(), true);
+        assert_eq!(board.perform_move(Move::new(Square::new(3, 0), Square::new(4, 0))).is_ok(), true);
+        assert_eq!(board.perform_move(Move::new(Square::new(0, 6), Square::new(0, 7))).is_ok(), true);
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(2, 0))).is_ok(), false);
+    }
+
+    #[test]
+    fn castling_in_move_generation() {
+        let board = castling_board();
+
+        assert_eq!(board.all_possible_moves_from_square(Square::new(4, 0)).count(), 7);
+        assert_eq!(board.all_possible_moves(Some(PieceColor::WHITE)).count(), 26);
+    }
+
+    #[test]
+    fn en_passant_capture() {
+        let mut board = Board::new();
+
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok(), true); // e4
+        assert_eq!(board.perform_move(Move::new(Square::new(0, 6), Square::new(0, 5))).is_ok(), true); // a6
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 3), Square::new(4, 4))).is_ok(), true); // e5
+        assert_eq!(board.perform_move(Move::new(Square::new(3, 6), Square::new(3, 4))).is_ok(), true); // d5
+
+        assert_eq!(board.en_passant(), Some(Square::new(3, 5)));
+        assert_eq!(board.all_possible_moves_from_square(Square::new(4, 4)).count(), 2);
+
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 4), Square::new(3, 5))).is_ok(), true); // exd6
+
+        assert_eq!(board.get_piece(Square::new(3, 5)).unwrap().piece_type(), PieceType::Pawn);
+        assert_eq!(board.get_piece(Square::new(3, 5)).unwrap().color(), PieceColor::WHITE);
+        assert!(board.get_piece(Square::new(3, 4)).is_none());
+        assert!(board.get_piece(Square::new(4, 4)).is_none());
+        assert_eq!(board.pieces(Some(PieceColor::BLACK)).count(), 15);
+    }
+
+    #[test]
+    fn en_passant_expires() {
+        let mut board = Board::new();
+
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok(), true); // e4
+        assert_eq!(board.perform_move(Move::new(Square::new(0, 6), Square::new(0, 5))).is_ok(), true); // a6
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 3), Square::new(4, 4))).is_ok(), true); // e5
+        assert_eq!(board.perform_move(Move::new(Square::new(3, 6), Square::new(3, 4))).is_ok(), true); // d5
+        assert_eq!(board.perform_move(Move::new(Square::new(7, 1), Square::new(7, 2))).is_ok(), true); // h3
+        assert_eq!(board.perform_move(Move::new(Square::new(7, 6), Square::new(7, 5))).is_ok(), true); // h6
+
+        assert_eq!(board.en_passant(), None);
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 4), Square::new(3, 5))).err().unwrap(), MoveFailReason::IllegalMove);
+    }
+
+    #[test]
+    fn en_passant_horizontal_pin() {
+        let mut board = Board::new_clear();
+
+        board.set(Square::new(0, 4), Some(Piece::new(PieceType::King, PieceColor::WHITE)));
+        board.set(Square::new(1, 4), Some(Piece::new(PieceType::Pawn, PieceColor::WHITE)));
+        board.set(Square::new(2, 6), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
+        board.set(Square::new(7, 4), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
+        board.set(Square::new(7, 7), Some(Piece::new(PieceType::King, PieceColor::BLACK)));
+        board.set_side_to_move(PieceColor::BLACK);
+
+        assert_eq!(board.perform_move(Move::new(Square::new(2, 6), Square::new(2, 4))).is_ok(), true); // c5
+
+        assert_eq!(board.en_passant(), Some(Square::new(2, 5)));
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 4), Square::new(2, 5))).err().unwrap(), MoveFailReason::KingAttacked);
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 4), Square::new(1, 5))).is_ok(), true);
+    }
+
+    #[test]
+    fn pawn_promotion() {
+        let mut board = Board::new_clear();
+
+        board.set(Square::new(0, 0), Some(Piece::new(PieceType::King, PieceColor::WHITE)));
+        board.set(Square::new(7, 3), Some(Piece::new(PieceType::King, PieceColor::BLACK)));
+        board.set(Square::new(4, 6), Some(Piece::new(PieceType::Pawn, PieceColor::WHITE)));
+        board.set(Square::new(3, 1), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
+
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 6), Square::new(4, 7))).err().unwrap(), MoveFailReason::PromotionRequired);
+        assert_eq!(board.check_move_possibility(Move::new_promotion(Square::new(4, 6), Square::new(4, 7), PieceType::King)).err().unwrap(), MoveFailReason::IllegalMove);
+        assert_eq!(board.check_move_possibility(Move::new_promotion(Square::new(4, 6), Square::new(4, 7), PieceType::Pawn)).err().unwrap(), MoveFailReason::IllegalMove);
+        assert_eq!(board.check_move_possibility(Move::new_promotion(Square::new(0, 0), Square::new(0, 1), PieceType::Queen)).err().unwrap(), MoveFailReason::IllegalMove);
+
+        assert_eq!(board.perform_move(Move::new_promotion(Square::new(4, 6), Square::new(4, 7), PieceType::Queen)).ok().unwrap(), PostMoveState::Normal);
+        assert_eq!(board.get_piece(Square::new(4, 7)).unwrap().piece_type(), PieceType::Queen);
+        assert_eq!(board.get_piece(Square::new(4, 7)).unwrap().color(), PieceColor::WHITE);
+        assert!(board.get_piece(Square::new(4, 6)).is_none());
+
+        assert_eq!(board.perform_move(Move::new_promotion(Square::new(3, 1), Square::new(3, 0), PieceType::Knight)).is_ok(), true);
+        assert_eq!(board.get_piece(Square::new(3, 0)).unwrap().piece_type(), PieceType::Knight);
+        assert_eq!(board.get_piece(Square::new(3, 0)).unwrap().color(), PieceColor::BLACK);
+    }
+
+    #[test]
+    fn pawn_promotion_in_move_generation() {
+        let mut board = Board::new_clear();
+
+        board.set(Square::new(4, 6), Some(Piece::new(PieceType::Pawn, PieceColor::WHITE)));
+        board.set(Square::new(3, 7), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
+
+        let moves: Vec<Move> = board.all_possible_moves_from_square(Square::new(4, 6)).collect();
+
+        assert_eq!(moves.len(), 8);
+        assert!(moves.iter().all(|m| m.promotion().is_some()));
+        assert!(moves.contains(&Move::new_promotion(Square::new(4, 6), Square::new(3, 7), PieceType::Knight)));
+    }
+
+    #[test]
+    fn err_wrong_turn() {
+        let mut board = Board::new();
+
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 6), Square::new(4, 4))).err().unwrap(), MoveFailReason::WrongTurn);
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok(), true);
+        assert_eq!(board.perform_move(Move::new(Square::new(3, 1), Square::new(3, 3))).err().unwrap(), MoveFailReason::WrongTurn);
+        assert_eq!(board.side_to_move(), PieceColor::BLACK);
+    }
+
+    #[test]
+    fn move_counters() {
+        let mut board = Board::new();
+
+        assert_eq!(board.halfmove_clock(), 0);
+        assert_eq!(board.fullmove_number(), 1);
+
+        assert_eq!(board.perform_move(Move::new(Square::new(6, 0), Square::new(5, 2))).is_ok(), true); // Nf3
+        assert_eq!(board.halfmove_clock(), 1);
+        assert_eq!(board.fullmove_number(), 1);
+
+        assert_eq!(board.perform_move(Move::new(Square::new(6, 7), Square::new(5, 5))).is_ok(), true); // Nf6
+        assert_eq!(board.halfmove_clock(), 2);
+        assert_eq!(board.fullmove_number(), 2);
+
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok(), true); // e4
+        assert_eq!(board.halfmove_clock(), 0);
+
+        assert_eq!(board.perform_move(Move::new(Square::new(1, 7), Square::new(2, 5))).is_ok(), true); // Nc6
+        assert_eq!(board.halfmove_clock(), 1);
+        assert_eq!(board.fullmove_number(), 3);
+
+        assert_eq!(board.perform_move(Move::new(Square::new(4, 3), Square::new(4, 4))).is_ok(), true); // e5
+        assert_eq!(board.perform_move(Move::new(Square::new(5, 5), Square::new(6, 3))).is_ok(), true); // Ng4
+        assert_eq!(board.halfmove_clock(), 1);
+
+        assert_eq!(board.perform_move(Move::new(Square::new(5, 2), Square::new(6, 0))).is_ok(), true); // Ng1
+        assert_eq!(board.perform_move(Move::new(Square::new(2, 5), Square::new(4, 4))).is_ok(), true); // Nxe5
+        assert_eq!(board.halfmove_clock(), 0);
+        assert_eq!(board.fullmove_number(), 5);
+    }
@@ -0,0 +1,179 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command as Process, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+use chess_api::board::*;
+use chess_api::cecp::*;
+use chess_api::engine::*;
+use chess_api::uci::CommandError;
+
+/// # Engine binary running in xboard mode
+struct EngineProcess {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>
+}
+
+impl EngineProcess {
+    fn start() -> EngineProcess {
+        let mut child = Process::new(env!("CARGO_BIN_EXE_engine"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, lines) = mpsc::channel();
+
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        writeln!(stdin, "xboard").unwrap();
+
+        EngineProcess { child, stdin, lines }
+    }
+
+    fn send(&mut self, command: &str) {
+        writeln!(self.stdin, "{}", command).unwrap();
+    }
+
+    /// # Returns lines up to and including the first one starting with given prefix
+    fn read_until(&self, prefix: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        loop {
+            let line = self.lines.recv_timeout(Duration::from_secs(30)).expect("engine did not answer");
+            let done = line.starts_with(prefix);
+
+            lines.push(line);
+
+            if done {
+                return lines;
+            }
+        }
+    }
+
+    fn quit(mut self) {
+        self.send("quit");
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!("protover 2".parse(), Ok(Command::Protover(2)));
+        assert_eq!("otim -50".parse(), Ok(Command::Otim(Duration::ZERO)));
+        assert_eq!("st 2.5".parse(), Ok(Command::St(Duration::from_millis(2500))));
+        assert_eq!("sd 7".parse(), Ok(Command::Sd(7)));
+        assert_eq!("?".parse(), Ok(Command::MoveNow));
+        assert_eq!("level 0 5 3".parse(), Ok(Command::Level { moves: 0, base: Duration::from_secs(300), increment: Duration::from_secs(3) }));
+        assert_eq!("setboard 8/8/8/8/8/8/8/K6k w - - 0 1".parse(), Ok(Command::SetBoard("8/8/8/8/8/8/8/K6k w - - 0 1".to_string())));
+        assert_eq!("result 1/2-1/2 {Draw by repetition}".parse(), Ok(Command::Result { result: "1/2-1/2".to_string(), comment: Some("Draw by repetition".to_string()) }));
+        assert_eq!("result *".parse(), Ok(Command::Result { result: "*".to_string(), comment: None }));
+
+        assert_eq!("level 40 x 0".parse::<Command>(), Err(CommandError::InvalidValue("x".to_string())));
+        assert_eq!("usermove".parse::<Command>(), Err(CommandError::MissingValue("usermove".to_string())));
+        assert_eq!("e2e4".parse::<Command>(), Err(CommandError::UnknownCommand("e2e4".to_string())));
+    }
+
+    #[test]
+    fn parse_engine_messages() {
+        assert_eq!("resign".parse(), Ok(EngineMessage::Resign));
+        assert_eq!("offer draw".parse(), Ok(EngineMessage::OfferDraw));
+        assert_eq!("pong 12".parse(), Ok(EngineMessage::Pong(12)));
+        assert_eq!("Illegal move (in check): e1e2".parse(), Ok(EngineMessage::IllegalMove("e1e2".to_string())));
+        assert_eq!("Error (unknown command): foo".parse(), Ok(EngineMessage::Error("foo".to_string())));
+        assert_eq!("0-1 {White resigns}".parse(), Ok(EngineMessage::Result { result: "0-1".to_string(), comment: Some("White resigns".to_string()) }));
+        assert!(matches!("12& -100005 30 1000 Qxf7+".parse(), Ok(EngineMessage::Thinking(Thinking { depth: 12, score: -100005, .. }))));
+        assert!("telluser hello".parse::<EngineMessage>().is_err());
+    }
+
+    #[test]
+    fn thinking_of_search_result() {
+        let board = Board::from_fen("7k/8/8/8/8/8/1R6/R6K w - - 0 1").unwrap();
+        let thinking = Thinking::new(&search(&board, SearchLimits::depth(4)), &board);
+
+        assert_eq!(thinking.score, MATE_SCORE + 2);
+        assert_eq!(thinking.pv.split(' ').count(), 3);
+        assert!(thinking.pv.ends_with('#'));
+
+        // time is sent in centiseconds
+        let parsed: Thinking = thinking.to_string().parse().unwrap();
+        assert_eq!(parsed.to_string(), thinking.to_string());
+    }
+
+    #[test]
+    fn engine_plays_both_sides_on_request() {
+        let mut engine = EngineProcess::start();
+
+        engine.send("protover 2");
+        let features = engine.read_until("feature");
+        assert!(features[0].contains("usermove=1") && features[0].ends_with("done=1"));
+
+        engine.send("new");
+        engine.send("sd 2");
+        engine.send("usermove e2e4");
+
+        let reply = engine.read_until("move").pop().unwrap();
+        assert!(parse_move(&Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap(), &reply[5..]).is_ok());
+
+        engine.send("usermove e2e4");
+        assert_eq!(engine.read_until("Illegal"), ["Illegal move: e2e4"]);
+
+        engine.send("ping 7");
+        assert_eq!(engine.read_until("pong"), ["pong 7"]);
+
+        engine.quit();
+    }
+
+    #[test]
+    fn engine_mates_from_set_board_and_reports_result() {
+        let mut engine = EngineProcess::start();
+
+        engine.send("new");
+        engine.send("force");
+        engine.send("setboard 6k1/5ppp/8/8/8/8/5PPP/3Q2K1 w - - 0 1");
+        engine.send("post");
+        engine.send("time 1000");
+        engine.send("otim 1000");
+        engine.send("go");
+
+        let lines = engine.read_until("1-0");
+        assert_eq!(lines[lines.len() - 2], "move d1d8");
+        assert_eq!(lines[lines.len() - 1], "1-0 {White mates}");
+        assert!(lines[0].starts_with("1 100001 ") && lines[0].ends_with(" Qd8#"));
+
+        engine.quit();
+    }
+
+    #[test]
+    fn force_mode_only_records_moves() {
+        let mut engine = EngineProcess::start();
+
+        engine.send("new");
+        engine.send("force");
+        engine.send("usermove e2e4");
+        engine.send("usermove e7e5");
+        engine.send("Nf3");
+        engine.send("ping 1");
+        assert_eq!(engine.read_until("pong"), ["pong 1"]);
+
+        // black to move after three moves
+        engine.send("sd 1");
+        engine.send("go");
+        let reply = engine.read_until("move").pop().unwrap();
+        let board = Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2").unwrap();
+
+        assert!(parse_move(&board, &reply[5..]).is_ok(), "{}", reply);
+
+        engine.quit();
+    }
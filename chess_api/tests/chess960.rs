@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+
+use chess_api::board::*;
+use chess_api::movement::*;
+use chess_api::pgn::*;
+use chess_api::piece::*;
+
+fn uci(m: &str) -> Move {
+    Move::from_uci(m).unwrap()
+}
+
+    #[test]
+    fn all_start_positions_are_distinct_and_valid() {
+        let mut positions = HashSet::new();
+
+        for id in 0..960 {
+            let board = Board::new_chess960(id);
+            let rank: Vec<PieceType> = (0..8).map(|x| board.get_piece(Square::new(x, 0)).unwrap().piece_type()).collect();
+            let files = |piece_type| (0..8).filter(|x| rank[*x] == piece_type).collect::<Vec<_>>();
+
+            let bishops = files(PieceType::Bishop);
+            let rooks = files(PieceType::Rook);
+            let king = files(PieceType::King)[0];
+
+            assert!(bishops[0] % 2 != bishops[1] % 2, "bishops of {}", id);
+            assert!(rooks[0] < king && king < rooks[1], "king of {}", id);
+            assert_eq!(files(PieceType::Knight).len(), 2);
+            assert_eq!(files(PieceType::Queen).len(), 1);
+
+            // black mirrors white and both can castle to both sides
+            for x in 0..8 {
+                assert_eq!(board.get_piece(Square::new(x, 7)).unwrap().piece_type(), rank[x as usize]);
+            }
+
+            assert!(board.to_fen().ends_with(" w KQkq - 0 1"));
+            assert!(board.is_chess960());
+            assert!(positions.insert(board.to_fen()));
+        }
+
+        assert_eq!(Board::new_chess960(518).to_fen(), Board::new().to_fen());
+        assert_eq!(Board::new_chess960(959).to_fen(), "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w KQkq - 0 1");
+    }
+
+    #[test]
+    fn switching_mode_updates_hash_with_castling_rights() {
+        let mut board = Board::new_chess960(0);
+
+        board.set_chess960(false);
+        assert_eq!(board.to_fen(), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w - - 0 1");
+        assert_eq!(board.hash(), Board::from_fen(&board.to_fen()).unwrap().hash());
+
+        board.set_chess960(true);
+        assert_eq!(board.hash(), Board::from_fen(&board.to_fen()).unwrap().hash());
+        assert_eq!(board.hash(), Board::new_chess960(0).hash());
+    }
+
+    #[test]
+    fn xfen_and_shredder_castling_rights() {
+        let board = Board::from_fen("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9").unwrap();
+
+        assert!(board.is_chess960());
+        assert_eq!(board.to_fen(), "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9");
+        assert_eq!(board.to_shredder_fen(), "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9");
+        assert_eq!(Board::from_fen(&board.to_fen()).unwrap().to_shredder_fen(), board.to_shredder_fen());
+
+        // file letter is needed when another rook stands further out
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/RR2K3 w B - 0 1").unwrap();
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/RR2K3 w B - 0 1");
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/RR2K3 w Q - 0 1").unwrap().to_fen(), "4k3/8/8/8/8/8/8/RR2K3 w Q - 0 1");
+
+        // standard rights keep standard castling, file letters switch to Chess960
+        assert!(!Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap().is_chess960());
+        assert!(Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w HAha - 0 1").unwrap().is_chess960());
+
+        let error = |fen: &str| Board::from_fen(fen).err().unwrap();
+        assert_eq!(error("4k3/8/8/8/8/8/8/RR2K3 w BA - 0 1"), FenError::InvalidCastling("BA".to_string()));
+        assert_eq!(error("4k3/8/8/8/8/8/8/RR2K3 w C - 0 1"), FenError::InvalidCastling("C".to_string()));
+        assert_eq!(error("4k3/8/8/8/8/8/8/RR2K3 w x - 0 1"), FenError::InvalidCastling("x".to_string()));
+    }
+
+    #[test]
+    fn castling_is_king_taking_own_rook() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/RK3R2 w AF - 0 1").unwrap();
+        let fen = board.to_fen();
+        let hash = board.hash();
+
+        let legal = board.generate_legal(MoveFilter::All);
+        assert!(legal.contains(&uci("b1a1")) && legal.contains(&uci("b1f1")));
+        assert!(!legal.contains(&uci("b1d1")));
+
+        assert_eq!(board.parse_san("O-O").unwrap(), uci("b1f1"));
+        assert_eq!(board.to_san(uci("b1a1")), "O-O-O");
+        assert!(!board.is_capture(uci("b1f1")));
+        assert_eq!(board.see(uci("b1f1")), 0);
+
+        // rook of king side castling stays on its square
+        let undo = board.apply_move(uci("b1f1"));
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/R4RK1 b - - 1 1");
+        board.undo_move(undo);
+        assert_eq!((board.to_fen(), board.hash()), (fen.clone(), hash));
+
+        let undo = board.apply_move(uci("b1a1"));
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/2KR1R2 b - - 1 1");
+        board.undo_move(undo);
+        assert_eq!((board.to_fen(), board.hash()), (fen, hash));
+
+        // king may stay on its square too
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/6KR w H - 0 1").unwrap();
+        board.perform_move(uci("g1h1")).unwrap();
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/5RK1 b - - 1 1");
+    }
+
+    #[test]
+    fn castling_legality_on_arbitrary_files() {
+        let error = |fen: &str, m: &str| Board::from_fen(fen).unwrap().check_move_possibility(uci(m)).err();
+
+        // rook has to pass d1
+        assert_eq!(error("4k3/8/8/8/8/8/8/RK1N4 w A - 0 1", "b1a1"), Some(MoveFailReason::IllegalMove));
+        // king would end on attacked g1
+        assert_eq!(error("4k1r1/8/8/8/8/8/8/RK3R2 w AF - 0 1", "b1f1"), Some(MoveFailReason::KingAttacked));
+        // king passes attacked d1
+        assert_eq!(error("3rk3/8/8/8/8/8/8/RK3R2 w AF - 0 1", "b1f1"), Some(MoveFailReason::KingAttacked));
+        assert_eq!(error("3rk3/8/8/8/8/8/8/RK3R2 w AF - 0 1", "b1a1"), None);
+        // castling rook shields c1 from the queen only before castling
+        assert_eq!(error("4k3/8/8/8/8/8/8/q1RK4 w Q - 0 1", "d1c1"), Some(MoveFailReason::KingAttacked));
+        // king in check can not castle
+        assert_eq!(error("1r2k3/8/8/8/8/8/8/RK3R2 w AF - 0 1", "b1a1"), Some(MoveFailReason::KingAttacked));
+
+        // moved rook loses the right, inner rook can still castle with the other
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/RK3R2 w AF - 0 1").unwrap();
+        for m in ["a1a2", "e8e7", "a2a1", "e7e8"] {
+            board.perform_move(uci(m)).unwrap();
+        }
+
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/RK3R2 w K - 4 3");
+        assert_eq!(board.check_move_possibility(uci("b1a1")).err(), Some(MoveFailReason::FriendlyFire));
+    }
+
+    #[test]
+    fn perft_chess960_positions() {
+        let perft = |fen: &str, depth| Board::from_fen(fen).unwrap().perft(depth);
+
+        // node counts from https://www.chessprogramming.org/Chess960_Perft_Results
+        assert_eq!(perft("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9", 4), 326672);
+        assert_eq!(perft("2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9", 3), 18002);
+        assert_eq!(perft("b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9", 3), 10471);
+    }
+
+    #[test]
+    fn pgn_variant_tag_switches_castling_notation() {
+        let fen = "4k3/8/8/8/8/8/8/RK3R2 w KQ - 0 1";
+        let game: PgnGame = format!("[Variant \"Chess960\"]\n[SetUp \"1\"]\n[FEN \"{}\"]\n\n1. O-O Kd7 *", fen).parse().unwrap();
+
+        assert!(game.start_board().is_chess960());
+        assert_eq!(game.moves[0].m, uci("b1f1"));
+
+        let written = PgnGame::from_game(&chess_api::game::Game::from_board(Board::new_chess960(518)));
+        assert_eq!(written.tag("Variant"), Some("Chess960"));
+        assert_eq!(written.tag("FEN"), Some(Board::new().to_fen().as_str()));
+        assert!(written.to_string().parse::<PgnGame>().unwrap().start_board().is_chess960());
+    }
@@ -0,0 +1,121 @@
+use std::time::{Duration, Instant};
+
+use chess_api::board::*;
+use chess_api::engine::*;
+
+/// # Returns FEN of the same position with colors swapped
+fn mirror(fen: &str) -> String {
+    let fields: Vec<&str> = fen.split(' ').collect();
+    let swap_case = |s: &str| s.chars().map(|c| if c.is_ascii_uppercase() { c.to_ascii_lowercase() } else { c.to_ascii_uppercase() }).collect::<String>();
+
+    let placement: Vec<String> = fields[0].split('/').rev().map(swap_case).collect();
+    let side = if fields[1] == "w" { "b" } else { "w" };
+    let en_passant = fields[3].replace('3', "x").replace('6', "3").replace('x', "6");
+
+    format!("{} {} {} {} {} {}", placement.join("/"), side, swap_case(fields[2]), en_passant, fields[4], fields[5])
+}
+
+    #[test]
+    fn evaluation_is_symmetric() {
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8"
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            let mirrored = Board::from_fen(&mirror(fen)).unwrap();
+
+            assert_eq!(evaluate(&board), evaluate(&mirrored), "{}", fen);
+        }
+    }
+
+    #[test]
+    fn finds_mate_in_two() {
+        let board = Board::from_fen("7k/8/8/8/8/8/1R6/R6K w - - 0 1").unwrap();
+        let best = search(&board, SearchLimits::depth(5));
+
+        assert_eq!(best.mate_in(), Some(2));
+        assert_eq!(best.pv().len(), 3);
+        assert_eq!(best.pv()[0], best.get_move().unwrap());
+    }
+
+    #[test]
+    fn sees_being_mated() {
+        // black can not stop Ra8 after any move
+        let board = Board::from_fen("7k/1R6/8/8/8/8/8/R6K b - - 0 1").unwrap();
+        let best = search(&board, SearchLimits::depth(4));
+
+        assert_eq!(best.mate_in(), Some(-1));
+    }
+
+    #[test]
+    fn mate_on_fiftieth_move_is_not_draw() {
+        let board = Board::from_fen("7k/8/5KQ1/8/8/8/8/8 w - - 99 80").unwrap();
+        let best = search(&board, SearchLimits::depth(3));
+
+        assert_eq!(best.get_move(), Some("g6g7".parse().unwrap()));
+        assert_eq!(best.mate_in(), Some(1));
+    }
+
+    #[test]
+    fn avoids_stalemate() {
+        let mut board = Board::from_fen("k7/8/1Q6/8/8/8/8/7K w - - 0 1").unwrap();
+        let best = search(&board, SearchLimits::depth(3));
+
+        assert_ne!(board.perform_move(best.get_move().unwrap()), Ok(PostMoveState::Stelemate));
+        assert!(best.score() > 500);
+    }
+
+    #[test]
+    fn wins_material_with_fork() {
+        // Nc7+ forks king and rook
+        let board = Board::from_fen("r3k3/8/8/3N4/8/8/8/4K3 w - - 0 1").unwrap();
+        let best = search(&board, SearchLimits::depth(4));
+
+        assert_eq!(best.get_move().unwrap().to_uci(), "d5c7");
+    }
+
+    #[test]
+    fn no_moves_when_game_is_over() {
+        let checkmated = Board::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+        let best = search(&checkmated, SearchLimits::depth(3));
+
+        assert_eq!(best.get_move(), None);
+        assert_eq!(best.score(), -MATE);
+
+        let stalemated = Board::from_fen("k7/8/1Q6/8/8/8/8/7K b - - 0 1").unwrap();
+        let best = search(&stalemated, SearchLimits::depth(3));
+
+        assert_eq!(best.get_move(), None);
+        assert_eq!(best.score(), 0);
+    }
+
+    #[test]
+    fn respects_node_limit() {
+        let best = search(&Board::new(), SearchLimits::nodes(2000));
+
+        assert!(best.get_move().is_some());
+        assert!(best.nodes() <= 2000);
+    }
+
+    #[test]
+    fn respects_time_limit() {
+        let start = Instant::now();
+        let best = search(&Board::new(), SearchLimits::time(Duration::from_millis(200)));
+
+        assert!(best.get_move().is_some());
+        assert!(best.depth() >= 1);
+        assert!(start.elapsed() < Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn engine_reuses_table() {
+        let board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let mut engine = Engine::new();
+
+        let first = engine.search(&board, SearchLimits::depth(3));
+        let second = engine.search(&board, SearchLimits::depth(3));
+
+        assert!(second.nodes() < first.nodes());
+        assert_eq!(first.get_move(), second.get_move());
+    }
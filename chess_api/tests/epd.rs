@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use chess_api::board::*;
+use chess_api::engine::Engine;
+use chess_api::epd::*;
+use chess_api::movement::*;
+
+fn uci(m: &str) -> Move {
+    Move::from_uci(m).unwrap()
+}
+
+    #[test]
+    fn standard_opcodes() {
+        let epd: Epd = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - bm Bb5 Bc4; am Nxe5; ce -15; pv Bb5 a6 Ba4; id \"Ruy.001\"; c0 \"Spanish; main line\"; c9 \"last\"; hmvc 2; fmvn 3;"
+            .parse()
+            .unwrap();
+
+        assert_eq!(epd.best_moves(), [uci("f1b5"), uci("f1c4")]);
+        assert_eq!(epd.avoid_moves(), [uci("f3e5")]);
+        assert_eq!(epd.centipawn_evaluation(), Some(-15));
+        assert_eq!(epd.predicted_variation(), [uci("f1b5"), uci("a7a6"), uci("b5a4")]);
+        assert_eq!(epd.id(), Some("Ruy.001"));
+        assert_eq!(epd.comment(0), Some("Spanish; main line"));
+        assert_eq!(epd.comment(9), Some("last"));
+        assert_eq!(epd.comment(1), None);
+        assert_eq!(epd.board.to_fen(), "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+
+        assert!(epd.is_solved_by(uci("f1c4")));
+        assert!(!epd.is_solved_by(uci("f3e5")));
+        assert!(!epd.is_solved_by(uci("d2d4")));
+    }
+
+    #[test]
+    fn unknown_opcodes_are_kept_and_written_back() {
+        let text = "4k3/8/8/8/8/8/4P3/4K3 w - - bm e4; ce +32765; dm 12; cx one; c7 \"seven\"; id \"king and pawn\"; noop;";
+        let epd: Epd = text.parse().unwrap();
+
+        assert_eq!(epd.operation("dm"), Some(&["12".to_string()][..]));
+        assert_eq!(epd.operation("noop"), Some(&[][..]));
+        assert_eq!(epd.to_string(), text);
+
+        // position without operations
+        let epd: Epd = "4k3/8/8/8/8/8/4P3/4K3 b - -".parse().unwrap();
+        assert!(epd.operations.is_empty());
+        assert!(!epd.is_solved_by(uci("e8e7")));
+    }
+
+    #[test]
+    fn invalid_records() {
+        let error = |text: &str| text.parse::<Epd>().err().unwrap();
+
+        assert_eq!(error("4k3/8/8/8/8/8/4P3/4K3 w -"), EpdError::MissingFields(3));
+        assert_eq!(error("4k3/8/8/8/8/8/4P3/4K3 x - - bm e4;"), EpdError::Fen(FenError::InvalidSideToMove("x".to_string())));
+        assert_eq!(error("4k3/8/8/8/8/8/4P3/4K3 w - - hmvc x;"), EpdError::Fen(FenError::InvalidHalfmoveClock("x".to_string())));
+        assert_eq!(error("4k3/8/8/8/8/8/4P3/4K3 w - - bm e4"), EpdError::Unterminated("bm".to_string()));
+        assert_eq!(error("4k3/8/8/8/8/8/4P3/4K3 w - - id \"open;"), EpdError::Unterminated("id".to_string()));
+        assert_eq!(error("4k3/8/8/8/8/8/4P3/4K3 w - - 1bm e4;"), EpdError::InvalidOpcode("1bm".to_string()));
+        assert_eq!(error("4k3/8/8/8/8/8/4P3/4K3 w - - ce high;"), EpdError::InvalidOperand { opcode: "ce".to_string(), operand: "high".to_string() });
+        assert_eq!(error("4k3/8/8/8/8/8/4P3/4K3 w - - bm e5;"), EpdError::Move { opcode: "bm".to_string(), error: SanError::Illegal("e5".to_string()) });
+
+        // moves of predicted variation are played one after another
+        assert!("4k3/8/8/8/8/8/4P3/4K3 w - - pv e4 e5;".parse::<Epd>().is_err());
+        assert!("4k3/8/8/8/8/8/4P3/4K3 w - - pv e4 Kd7 e5;".parse::<Epd>().is_ok());
+    }
+
+    #[test]
+    fn suite_file_is_read_with_line_numbers() {
+        let text = "# mates\n6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - bm Rd8#;\n\n4k3/8/8/8/8/8/4P3/4K3 w - - bm Kd2 e4;\n";
+
+        assert_eq!(read_epd(text).unwrap().len(), 2);
+        assert_eq!(read_epd("4k3/8/8/8/8/8/4P3/4K3 w - - bm e4;\n\n4k3/8 w - - bm e4;").err().unwrap().0, 3);
+    }
+
+    #[test]
+    fn suite_reports_solved_and_failed_positions() {
+        let suite = read_epd(concat!(
+            "6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - bm Rd8#; id \"mate in one\";\n",
+            // winning the queen is found, the wrong expectation fails
+            "4k3/8/8/3q4/8/8/8/3RK3 w - - bm Kf2; id \"wrong\";\n",
+            "4k3/8/8/8/8/8/8/4K3 w - -\n"
+        )).unwrap();
+
+        let mut seen = Vec::new();
+        let report = run_suite(&mut Engine::new(), &suite, Duration::from_millis(100), |result| seen.push(result.id.clone()));
+
+        assert_eq!(seen, ["mate in one", "wrong", "3"]);
+        assert_eq!(report.time, Duration::from_millis(100));
+        assert_eq!(report.solved(), 1);
+        assert_eq!(report.results[0].found, Some(uci("d1d8")));
+        assert_eq!(report.results[1].found, Some(uci("d1d5")));
+        assert_eq!(report.failed().map(|result| result.id.as_str()).collect::<Vec<_>>(), ["wrong", "3"]);
+    }
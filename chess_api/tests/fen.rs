@@ -0,0 +1,82 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use chess_api::board::*;
+use chess_api::movement::*;
+use chess_api::piece::*;
+
+const POSITIONS: [&str; 6] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8"
+];
+
+    #[test]
+    fn fen_round_trip() {
+        for fen in POSITIONS {
+            assert_eq!(Board::from_fen(fen).unwrap().to_fen(), fen);
+        }
+    }
+
+    #[test]
+    fn fen_after_moves() {
+        let mut board = Board::new();
+
+        assert!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok()); // e4
+        assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+
+        assert!(board.perform_move(Move::new(Square::new(6, 7), Square::new(5, 5))).is_ok()); // Nf6
+        assert!(board.perform_move(Move::new(Square::new(4, 0), Square::new(4, 1))).is_ok()); // Ke2
+        assert!(board.perform_move(Move::new(Square::new(7, 7), Square::new(6, 7))).is_ok()); // Rg8
+        assert_eq!(board.to_fen(), "rnbqkbr1/pppppppp/5n2/8/4P3/8/PPPPKPPP/RNBQ1BNR w q - 3 3");
+    }
+
+    #[test]
+    fn fen_import() {
+        let board = Board::from_fen(POSITIONS[2]).unwrap();
+
+        assert_eq!(board.pieces(None).count(), 32);
+        assert_eq!(board.get_piece(Square::new(4, 6)).unwrap().piece_type(), PieceType::Queen);
+        assert_eq!(board.get_piece(Square::new(4, 6)).unwrap().color(), PieceColor::BLACK);
+        assert_eq!(board.all_possible_moves(Some(PieceColor::WHITE)).count(), 48);
+
+        let board = Board::from_fen(POSITIONS[5]).unwrap();
+
+        assert_eq!(board.halfmove_clock(), 1);
+        assert_eq!(board.fullmove_number(), 8);
+        assert_eq!(board.all_possible_moves(Some(PieceColor::WHITE)).count(), 44);
+    }
+
+    #[test]
+    fn fen_castling_rights_and_pawns() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/4P3/R3K2R w Kq - 0 1").unwrap();
+
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(6, 0))).is_ok(), true);
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 0), Square::new(2, 0))).is_ok(), false);
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok(), true);
+
+        let board = Board::from_fen("4k3/8/8/8/8/4P3/8/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(4, 2), Square::new(4, 4))).is_ok(), false);
+    }
+
+    #[test]
+    fn fen_errors() {
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/8 w - - 0").err().unwrap(), FenError::FieldCount(5));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8 w - - 0 1").err().unwrap(), FenError::RankCount(7));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/7 w - - 0 1").err().unwrap(), FenError::RankLength(1));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/4k4 w - - 0 1").err().unwrap(), FenError::RankLength(1));
+        assert_eq!(Board::from_fen("ppppppppp/8/8/8/8/8/8/8 w - - 0 1").err().unwrap(), FenError::RankLength(8));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/3x4 w - - 0 1").err().unwrap(), FenError::InvalidPiece('x'));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/8 x - - 0 1").err().unwrap(), FenError::InvalidSideToMove("x".to_string()));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/R3K3 w K - 0 1").err().unwrap(), FenError::InvalidCastling("K".to_string()));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/R3K2R w KK - 0 1").err().unwrap(), FenError::InvalidCastling("KK".to_string()));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/8 w - e3 0 1").err().unwrap(), FenError::InvalidEnPassant("e3".to_string()));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/8 w - z6 0 1").err().unwrap(), FenError::InvalidEnPassant("z6".to_string()));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/8 w - - x 1").err().unwrap(), FenError::InvalidHalfmoveClock("x".to_string()));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/8 w - - 0 0").err().unwrap(), FenError::InvalidFullmoveNumber("0".to_string()));
+
+        assert_eq!(FenError::FieldCount(5).to_string(), "expected 6 fields, found 5");
+    }
@@ -0,0 +1,167 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use chess_api::board::*;
+use chess_api::game::*;
+use chess_api::movement::*;
+use chess_api::piece::*;
+
+    fn play(game: &mut Game, moves: &[&str]) -> Vec<String> {
+        let mut fens = vec![game.board().to_fen()];
+
+        for san in moves {
+            assert!(game.play_san(san).is_ok(), "{}", san);
+            fens.push(game.board().to_fen());
+        }
+
+        fens
+    }
+
+    fn undo_all(game: &mut Game, fens: &[String]) {
+        for fen in fens.iter().rev().skip(1) {
+            assert!(game.undo_move().is_some());
+            assert_eq!(&game.board().to_fen(), fen);
+        }
+
+        assert!(game.undo_move().is_none());
+    }
+
+    #[test]
+    fn game_history() {
+        let mut game = Game::new();
+
+        play(&mut game, &["e4", "e5", "Bc4", "Nc6", "Qh5", "Nf6", "Qxf7#"]);
+
+        let sans: Vec<&str> = game.moves().map(|m| m.san()).collect();
+
+        assert_eq!(sans, ["e4", "e5", "Bc4", "Nc6", "Qh5", "Nf6", "Qxf7#"]);
+        assert_eq!(game.last_move().unwrap().state(), PostMoveState::Checkmate);
+        assert_eq!(game.last_move().unwrap().get_move(), Move::from_uci("h5f7").unwrap());
+    }
+
+    #[test]
+    fn undo_restores_castling_and_en_passant() {
+        let mut game = Game::new();
+
+        let fens = play(&mut game, &["e4", "Nf6", "e5", "d5", "exd6", "e6", "Nf3", "Be7", "Bb5+", "c6", "O-O", "O-O", "dxe7", "Qxe7", "Re1", "cxb5"]);
+
+        undo_all(&mut game, &fens);
+
+        // pawns and kings can move the same way as before
+        assert_eq!(game.board().check_move_possibility(Move::from_uci("e2e4").unwrap()).is_ok(), true);
+        assert_eq!(game.board().to_fen(), Board::new().to_fen());
+    }
+
+    #[test]
+    fn undo_restores_promotion_and_captured_piece() {
+        let mut game = Game::from_board(Board::from_fen("r3k3/1P6/8/8/8/8/8/4K2R w Kq - 0 1").unwrap());
+
+        let fens = play(&mut game, &["bxa8=Q+", "Kd7", "Qxa7+", "Ke6", "O-O"]);
+
+        assert_eq!(game.board().pieces(Some(PieceColor::BLACK)).count(), 1);
+
+        undo_all(&mut game, &fens);
+
+        assert_eq!(game.board().get_piece(Square::new(1, 6)).unwrap().piece_type(), PieceType::Pawn);
+        assert_eq!(game.board().get_piece(Square::new(0, 7)).unwrap().piece_type(), PieceType::Rook);
+        assert_eq!(game.board().check_move_possibility(Move::from_uci("e1g1").unwrap()).is_ok(), true);
+    }
+
+    #[test]
+    fn redo_replays_moves() {
+        let mut game = Game::new();
+
+        let fens = play(&mut game, &["d4", "d5", "c4", "dxc4"]);
+
+        assert_eq!(game.undo_move(), Some(Move::from_uci("d5c4").unwrap()));
+        assert_eq!(game.undo_move(), Some(Move::from_uci("c2c4").unwrap()));
+        assert_eq!(game.redo_move(), Some(PostMoveState::Normal));
+        assert_eq!(game.board().to_fen(), fens[3]);
+        assert_eq!(game.redo_move(), Some(PostMoveState::Normal));
+        assert_eq!(game.board().to_fen(), fens[4]);
+        assert_eq!(game.redo_move(), None);
+
+        assert!(game.undo_move().is_some());
+        assert!(game.play_san("e6").is_ok());
+        assert_eq!(game.redo_move(), None);
+        assert_eq!(game.moves().count(), 4);
+    }
+
+    #[test]
+    fn game_errors() {
+        let mut game = Game::new();
+
+        assert_eq!(game.play_san("e5").err().unwrap(), GameError::San(SanError::Illegal("e5".to_string())));
+        assert_eq!(game.play(Move::from_uci("e7e5").unwrap()).err().unwrap(), MoveFailReason::WrongTurn);
+        assert_eq!(game.moves().count(), 0);
+    }
+
+    #[test]
+    fn repetition_draws() {
+        let mut game = Game::new();
+
+        let cycle = ["Nf3", "Nf6", "Ng1", "Ng8"];
+
+        play(&mut game, &cycle);
+        play(&mut game, &cycle);
+
+        assert_eq!(game.repetition_count(), 3);
+        assert_eq!(game.claimable_draw(), Some(DrawReason::ThreefoldRepetition));
+        assert_eq!(game.last_move().unwrap().state(), PostMoveState::Normal);
+
+        play(&mut game, &cycle);
+        play(&mut game, &cycle[..3]);
+
+        assert_eq!(game.repetition_count(), 4);
+        assert!(game.play_san("Ng8").is_ok());
+        assert_eq!(game.last_move().unwrap().state(), PostMoveState::Draw(DrawReason::FivefoldRepetition));
+
+        assert!(game.undo_move().is_some());
+        assert_eq!(game.repetition_count(), 4);
+    }
+
+    #[test]
+    fn repetition_ignores_impossible_en_passant() {
+        let mut game = Game::new();
+
+        // position after 1.e4 differs from the later one only by en passant square no pawn can use
+        play(&mut game, &["e4", "Nf6", "Nf3", "Ng8", "Ng1", "Nf6", "Nf3", "Ng8", "Ng1"]);
+
+        assert_eq!(game.repetition_count(), 3);
+    }
+
+    #[test]
+    fn fifty_and_seventy_five_move_rules() {
+        let mut game = Game::from_board(Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 98 80").unwrap());
+
+        assert_eq!(game.claimable_draw(), None);
+        assert!(game.play_san("Ra2").is_ok());
+        assert!(game.play_san("Kd8").is_ok());
+        assert_eq!(game.claimable_draw(), Some(DrawReason::FiftyMoveRule));
+
+        let mut game = Game::from_board(Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 148 80").unwrap());
+
+        assert_eq!(game.play_san("Ra2").ok().unwrap(), PostMoveState::Normal);
+        assert_eq!(game.play_san("Kd8").ok().unwrap(), PostMoveState::Draw(DrawReason::SeventyFiveMoveRule));
+
+        // checkmate takes precedence
+        let mut game = Game::from_board(Board::from_fen("3k4/8/3K4/8/8/8/8/R7 w - - 149 80").unwrap());
+
+        assert_eq!(game.play_san("Ra8#").ok().unwrap(), PostMoveState::Checkmate);
+    }
+
+    #[test]
+    fn insufficient_material() {
+        let mut game = Game::from_board(Board::from_fen("4k3/8/8/8/8/8/3r4/4K3 w - - 0 1").unwrap());
+
+        assert_eq!(game.play_san("Kxd2").ok().unwrap(), PostMoveState::Draw(DrawReason::InsufficientMaterial));
+        assert!(DrawReason::InsufficientMaterial.is_automatic());
+        assert!(!DrawReason::ThreefoldRepetition.is_automatic());
+
+        let mut game = Game::from_board(Board::from_fen("4k3/8/8/8/8/8/3r4/4KN2 w - - 0 1").unwrap());
+
+        assert_eq!(game.play_san("Kxd2").ok().unwrap(), PostMoveState::Draw(DrawReason::InsufficientMaterial));
+
+        let mut game = Game::from_board(Board::from_fen("4k3/8/8/8/8/8/3r4/4KNN1 w - - 0 1").unwrap());
+
+        assert_eq!(game.play_san("Kxd2").ok().unwrap(), PostMoveState::Normal);
+    }
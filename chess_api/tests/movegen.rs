@@ -0,0 +1,103 @@
+use chess_api::board::*;
+use chess_api::movement::*;
+use chess_api::piece::*;
+
+const POSITIONS: [&str; 5] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8"
+];
+
+fn sorted(moves: impl IntoIterator<Item = Move>) -> Vec<String> {
+    let mut moves: Vec<String> = moves.into_iter().map(|m| m.to_uci()).collect();
+    moves.sort();
+    moves
+}
+
+    #[test]
+    fn legal_matches_possible_moves() {
+        for fen in POSITIONS {
+            let board = Board::from_fen(fen).unwrap();
+
+            assert_eq!(sorted(board.generate_legal(MoveFilter::All)), sorted(board.all_possible_moves(Some(board.side_to_move()))));
+        }
+    }
+
+    #[test]
+    fn captures_and_quiet_moves_partition_legal_moves() {
+        for fen in POSITIONS {
+            let board = Board::from_fen(fen).unwrap();
+
+            let captures = board.generate_legal(MoveFilter::Captures);
+            let quiet = board.generate_legal(MoveFilter::Quiet);
+
+            assert!(captures.iter().all(|m| board.get_piece(m.end()).is_some() || board.en_passant() == Some(m.end())));
+            assert!(quiet.iter().all(|m| board.get_piece(m.end()).is_none()));
+            assert_eq!(sorted(captures.into_iter().chain(quiet)), sorted(board.generate_legal(MoveFilter::All)));
+        }
+
+        let board = Board::from_fen(POSITIONS[1]).unwrap();
+        assert_eq!(board.generate_legal(MoveFilter::Captures).len(), 8);
+    }
+
+    #[test]
+    fn en_passant_is_capture() {
+        let board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+
+        assert_eq!(sorted(board.generate_legal(MoveFilter::Captures)), ["e5d6"]);
+    }
+
+    #[test]
+    fn checks() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/3N4/R3K3 w Q - 0 1").unwrap();
+        assert_eq!(sorted(board.generate_legal(MoveFilter::Checks)), ["a1a8"]);
+
+        // every knight move discovers check from the rook
+        let board = Board::from_fen("4k3/8/8/8/4N3/8/8/4R1K1 w - - 0 1").unwrap();
+        assert_eq!(sorted(board.generate_legal(MoveFilter::Checks)), ["e4c3", "e4c5", "e4d2", "e4d6", "e4f2", "e4f6", "e4g3", "e4g5"]);
+    }
+
+    #[test]
+    fn evasions() {
+        let board = Board::from_fen("7k/8/8/4r3/8/8/R7/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(sorted(board.generate_legal(MoveFilter::Evasions)), ["a2e2", "e1d1", "e1d2", "e1f1", "e1f2"]);
+        assert_eq!(sorted(board.generate_pseudo_legal(MoveFilter::Evasions)), sorted(board.generate_legal(MoveFilter::All)));
+
+        assert!(Board::new().generate_legal(MoveFilter::Evasions).is_empty());
+    }
+
+    #[test]
+    fn pseudo_legal_includes_pinned_moves() {
+        let board = Board::from_fen("4k3/4r3/8/8/8/8/4B3/4K3 w - - 0 1").unwrap();
+
+        let pseudo_legal = board.generate_pseudo_legal(MoveFilter::All);
+        let legal = board.generate_legal(MoveFilter::All);
+
+        assert!(pseudo_legal.contains(&Move::new(Square::new(4, 1), Square::new(3, 2))));
+        assert!(!legal.contains(&Move::new(Square::new(4, 1), Square::new(3, 2))));
+        assert!(legal.iter().all(|m| pseudo_legal.contains(m)));
+    }
+
+    #[test]
+    fn only_side_to_move_generates() {
+        let mut board = Board::new();
+
+        assert!(board.generate_legal(MoveFilter::All).iter().all(|m| board.get_piece(m.start()).unwrap().color() == PieceColor::WHITE));
+
+        board.set_side_to_move(PieceColor::BLACK);
+        assert!(board.generate_legal(MoveFilter::All).iter().all(|m| board.get_piece(m.start()).unwrap().color() == PieceColor::BLACK));
+    }
+
+    #[test]
+    fn promotions_are_expanded() {
+        let board = Board::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+
+        let captures = board.generate_legal(MoveFilter::Captures);
+        let quiet = board.generate_legal(MoveFilter::Quiet);
+
+        assert_eq!(sorted(captures), ["a7b8b", "a7b8n", "a7b8q", "a7b8r"]);
+        assert_eq!(quiet.iter().filter(|m| m.promotion().is_some()).count(), 4);
+    }
@@ -0,0 +1,187 @@
+use chess_api::board::*;
+use chess_api::movement::*;
+use chess_api::pgn::*;
+
+const LICHESS: &str = r#"[Event "Rated Blitz game"]
+[Site "https://lichess.org/abcdefgh"]
+[Date "2024.03.01"]
+[Round "-"]
+[White "alice"]
+[Black "bob"]
+[Result "1-0"]
+[WhiteElo "1850"]
+[TimeControl "180+2"]
+
+1. e4 { [%clk 0:03:00] } 1... e5 { [%clk 0:03:00] } 2. Bc4 { [%clk 0:02:58] } 2... Nc6 { [%clk 0:02:57] }
+3. Qh5 { [%clk 0:02:55] } 3... Nf6?? { [%clk 0:02:50] } 4. Qxf7# { [%clk 0:02:53] } 1-0
+
+[Event "Casual game"]
+[Site "?"]
+[Date "????.??.??"]
+[Round "?"]
+[White "bob"]
+[Black "alice"]
+[Result "1/2-1/2"]
+
+1. d4 d5 1/2-1/2
+"#;
+
+    #[test]
+    fn multiple_games_with_tags_and_comments() {
+        let games = read_pgn(LICHESS).unwrap();
+
+        assert_eq!(games.len(), 2);
+
+        let first = &games[0];
+        assert_eq!(first.tags.len(), 9);
+        assert_eq!(first.tag("White"), Some("alice"));
+        assert_eq!(first.tag("TimeControl"), Some("180+2"));
+        assert_eq!(first.tag("Opening"), None);
+        assert_eq!(first.result, "1-0");
+        assert_eq!(first.moves.iter().map(|m| m.san.as_str()).collect::<Vec<_>>(), ["e4", "e5", "Bc4", "Nc6", "Qh5", "Nf6", "Qxf7#"]);
+        assert_eq!(first.moves[5].nags, [4]);
+        assert_eq!(first.moves[6].comments, ["[%clk 0:02:53]"]);
+
+        let game = first.to_game();
+        assert_eq!(game.last_move().unwrap().state(), PostMoveState::Checkmate);
+
+        assert_eq!(games[1].tag("Date"), Some("????.??.??"));
+        assert_eq!(games[1].result, "1/2-1/2");
+        assert_eq!(games[1].moves.len(), 2);
+    }
+
+    #[test]
+    fn nested_variations_are_played_from_their_position() {
+        let game: PgnGame = "1. e4 e5 (1... c5 2. Nf3 (2. Nc3 Nc6 (2... d6)) 2... d6) (1... e6) 2. Nf3 *".parse().unwrap();
+
+        assert_eq!(game.moves.len(), 3);
+
+        let variations = &game.moves[1].variations;
+        assert_eq!(variations.len(), 2);
+        assert_eq!(variations[0].iter().map(|m| m.san.as_str()).collect::<Vec<_>>(), ["c5", "Nf3", "d6"]);
+        assert_eq!(variations[1][0].san, "e6");
+
+        let nested = &variations[0][1].variations[0];
+        assert_eq!(nested[0].m, Move::from_uci("b1c3").unwrap());
+        assert_eq!(nested[1].variations[0][0].m, Move::from_uci("d7d6").unwrap());
+
+        assert_eq!(game.moves[2].m, Move::from_uci("g1f3").unwrap());
+    }
+
+    #[test]
+    fn annotations_and_comment_forms() {
+        let text = "% exported by hand\n{Game starts} 1.e4!? $14 e5?! ; rest of line\n2.Nf3 {two\n   lines} Nc6 $201 *";
+        let game: PgnGame = text.parse().unwrap();
+
+        assert_eq!(game.moves[0].comments_before, ["Game starts"]);
+        assert_eq!(game.moves[0].nags, [5, 14]);
+        assert_eq!(game.moves[1].nags, [6]);
+        assert_eq!(game.moves[1].comments, ["rest of line"]);
+        assert_eq!(game.moves[2].comments, ["two lines"]);
+        assert_eq!(game.moves[3].nags, [201]);
+        assert_eq!(game.result, "*");
+    }
+
+    #[test]
+    fn game_from_custom_position() {
+        let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 0 1";
+        let game: PgnGame = format!("[SetUp \"1\"]\n[FEN \"{}\"]\n\n1... Kd7 2. e4 *", fen).parse().unwrap();
+
+        assert_eq!(game.start_board().to_fen(), fen);
+        assert_eq!(game.to_game().board().to_fen(), "8/3k4/8/8/4P3/8/8/4K3 b - e3 0 2");
+    }
+
+    #[test]
+    fn missing_result_and_escaped_tag_value() {
+        let games = read_pgn("[Event \"The \\\"big\\\" one\"]\n1. e4\n[Event \"next\"]\n1. d4").unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].tag("Event"), Some("The \"big\" one"));
+        assert_eq!(games[0].result, "*");
+        assert_eq!(games[1].moves[0].san, "d4");
+
+        assert!(read_pgn("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn illegal_move_is_reported_with_position() {
+        let error = read_pgn("[Event \"x\"]\n\n1. e4 e5\n2. Ke3 Nc6 *").unwrap_err();
+
+        assert_eq!(error, PgnError { line: 4, column: 4, kind: PgnErrorKind::Move(SanError::Illegal("Ke3".to_string())) });
+        assert_eq!(error.to_string(), "line 4, column 4: 'Ke3' is not a legal move");
+
+        // moves in variations are checked in position before the replaced move
+        let error = read_pgn("1. e4 e5 (1... Nf3) *").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 16));
+        assert!(matches!(error.kind, PgnErrorKind::Move(SanError::Illegal(_))));
+
+        let error = read_pgn("1. e4 Zz5 *").unwrap_err();
+        assert_eq!(error.kind, PgnErrorKind::Move(SanError::Invalid("Zz5".to_string())));
+    }
+
+    #[test]
+    fn malformed_text_is_reported_with_position() {
+        let error = |text: &str| {
+            let error = read_pgn(text).unwrap_err();
+            (error.line, error.column, error.kind)
+        };
+
+        assert_eq!(error("[Event x]"), (1, 1, PgnErrorKind::InvalidTag));
+        assert_eq!(error("1. e4 {never closed\n"), (1, 7, PgnErrorKind::Unterminated));
+        assert_eq!(error("1. e4 e5 (1... c5 *"), (1, 19, PgnErrorKind::ResultInVariation("*".to_string())));
+        assert_eq!(error("1. e4 e5 (1... c5"), (1, 18, PgnErrorKind::UnclosedVariation));
+        assert_eq!(error("1. e4 ) *"), (1, 7, PgnErrorKind::UnexpectedVariationEnd));
+        assert_eq!(error("$1 e4 *"), (1, 1, PgnErrorKind::NoMove));
+        assert_eq!(error("1. e4 $300 *"), (1, 7, PgnErrorKind::InvalidNag("$300".to_string())));
+        assert_eq!(error("1. e4\n  & *"), (2, 3, PgnErrorKind::UnexpectedCharacter('&')));
+        assert_eq!(error("[FEN \"8/8 w - - 0 1\"]\n*"), (1, 1, PgnErrorKind::Fen(FenError::RankCount(2))));
+    }
+
+    #[test]
+    fn reader_stops_after_error() {
+        let mut reader = PgnReader::new("1. e4 *\n\n1. e5 *\n\n1. d4 *");
+
+        assert!(matches!(reader.next(), Some(Ok(_))));
+        assert!(matches!(reader.next(), Some(Err(_))));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn written_game_reads_back_the_same() {
+        let text = "[Event \"Analysis \\\"deep\\\"\"]\n\n1. e4 e5 (1... c5 2. Nf3 (2. Nc3 Nc6 (2... d6)) 2... d6) (1... e6) 2. Nf3 $1 {good} 2... Nc6 *";
+        let game: PgnGame = text.parse().unwrap();
+        let written = game.to_string();
+
+        assert_eq!(written, "[Event \"Analysis \\\"deep\\\"\"]\n\n1. e4 e5 (1... c5 2. Nf3 (2. Nc3 Nc6 (2... d6)) 2... d6) (1... e6) 2. Nf3 $1\n{good} 2... Nc6 *\n");
+        assert_eq!(written.parse::<PgnGame>().unwrap(), game);
+    }
+
+    #[test]
+    fn game_is_exported_with_tags_clocks_and_wrapped_lines() {
+        let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 0 1";
+        let mut game = chess_api::game::Game::from_board(Board::from_fen(fen).unwrap());
+
+        for san in ["Kd7", "e4", "Ke6", "Ke2", "Ke5", "Ke3", "Kd6", "Kf4", "Ke6", "Kg5", "Kf7", "Kf5"] {
+            game.play_san(san).unwrap();
+        }
+
+        let mut pgn = PgnGame::from_game(&game);
+        pgn.set_tag("TimeControl", "300+3");
+        pgn.set_result("1-0");
+        pgn.set_clocks(&(0..12).map(|n| std::time::Duration::from_secs(3600 + 300 - n * 7)).collect::<Vec<_>>());
+
+        let written = pgn.to_string();
+        let lines: Vec<&str> = written.lines().collect();
+
+        assert_eq!(&lines[..10], [
+            "[Event \"?\"]", "[Site \"?\"]", "[Date \"????.??.??\"]", "[Round \"?\"]", "[White \"?\"]", "[Black \"?\"]",
+            "[Result \"1-0\"]", "[SetUp \"1\"]", &format!("[FEN \"{}\"]", fen), "[TimeControl \"300+3\"]"
+        ]);
+        assert!(lines[11].starts_with("1... Kd7 {[%clk 1:05:00]} 2. e4 {[%clk 1:04:53]} 2... Ke6 {[%clk 1:04:46]}"));
+        assert!(lines[11..].iter().all(|line| line.len() <= 80));
+        assert!(written.ends_with("{[%clk 1:03:43]} 1-0\n"));
+
+        let read: PgnGame = written.parse().unwrap();
+        assert_eq!(read.to_game().board().to_fen(), game.board().to_fen());
+        assert_eq!(read.moves[11].comments, ["[%clk 1:03:43]"]);
+    }
@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use chess_api::board::*;
 use chess_api::piece::*;
 use chess_api::movement::*;
@@ -25,6 +27,7 @@ use chess_api::movement::*;
     #[test]
     fn basic_black_pawn_movement() {
         let mut board = Board::new_clear();
+        board.set_side_to_move(PieceColor::BLACK);
 
         board.set(Square::new(3, 3), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
     
@@ -61,6 +64,7 @@ use chess_api::movement::*;
     #[test]
     fn blocked_basic_black_pawn_movement() {
         let mut board = Board::new_clear();
+        board.set_side_to_move(PieceColor::BLACK);
 
         board.set(Square::new(3, 3), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
         board.set(Square::new(3, 1), Some(Piece::new(PieceType::Pawn, PieceColor::WHITE)));
@@ -106,6 +110,7 @@ use chess_api::movement::*;
     #[test]
     fn black_pawn_taking_opponent_pieces() {
         let mut board = Board::new_clear();
+        board.set_side_to_move(PieceColor::BLACK);
 
         board.set(Square::new(3, 3), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
         board.set(Square::new(4, 1), Some(Piece::new(PieceType::Pawn, PieceColor::WHITE)));
@@ -146,6 +151,7 @@ use chess_api::movement::*;
     #[test]
     fn black_pawn_taking_allied_pieces() {
         let mut board = Board::new_clear();
+        board.set_side_to_move(PieceColor::BLACK);
 
         board.set(Square::new(3, 3), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
         board.set(Square::new(4, 2), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
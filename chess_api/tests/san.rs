@@ -0,0 +1,69 @@
+use chess_api::board::*;
+use chess_api::movement::*;
+use chess_api::piece::*;
+
+    fn play(board: &mut Board, moves: &[&str]) {
+        for san in moves {
+            let m = board.parse_san(san).unwrap();
+
+            assert_eq!(board.to_san(m), *san);
+            assert!(board.perform_move(m).is_ok());
+        }
+    }
+
+    #[test]
+    fn san_game() {
+        let mut board = Board::new();
+
+        // scholar's mate
+        play(&mut board, &["e4", "e5", "Bc4", "Nc6", "Qh5", "Nf6", "Qxf7#"]);
+    }
+
+    #[test]
+    fn san_castling_and_captures() {
+        let mut board = Board::new();
+
+        play(&mut board, &["e4", "d5", "exd5", "Qxd5", "Nc3", "Qa5", "Nf3", "Bg4", "Be2", "Nc6", "O-O", "O-O-O"]);
+
+        assert_eq!(board.get_piece(Square::new(6, 0)).unwrap().piece_type(), PieceType::King);
+        assert_eq!(board.get_piece(Square::new(2, 7)).unwrap().piece_type(), PieceType::King);
+    }
+
+    #[test]
+    fn san_en_passant_and_promotion() {
+        let mut board = Board::from_fen("7k/1P6/8/3pP3/8/8/8/K7 w - d6 0 1").unwrap();
+
+        play(&mut board, &["exd6", "Kh7", "b8=Q", "Kg6", "d7", "Kf7", "d8=N+"]);
+    }
+
+    #[test]
+    fn san_disambiguation() {
+        let board = Board::from_fen("6k1/8/8/8/R6R/8/8/R3K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.to_san(Move::new(Square::new(0, 3), Square::new(3, 3))), "Rad4");
+        assert_eq!(board.to_san(Move::new(Square::new(7, 3), Square::new(3, 3))), "Rhd4");
+        assert_eq!(board.to_san(Move::new(Square::new(0, 3), Square::new(0, 2))), "R4a3");
+        assert_eq!(board.to_san(Move::new(Square::new(0, 0), Square::new(0, 2))), "R1a3");
+
+        assert_eq!(board.parse_san("Rhd4").unwrap(), Move::new(Square::new(7, 3), Square::new(3, 3)));
+        assert_eq!(board.parse_san("R1a3").unwrap(), Move::new(Square::new(0, 0), Square::new(0, 2)));
+        assert_eq!(board.parse_san("Ra4a3").unwrap(), Move::new(Square::new(0, 3), Square::new(0, 2)));
+
+        let board = Board::from_fen("k7/8/8/8/1Q1Q4/8/1Q6/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.to_san(Move::new(Square::new(1, 3), Square::new(2, 2))), "Qb4c3");
+        assert_eq!(board.to_san(Move::new(Square::new(1, 1), Square::new(2, 2))), "Q2c3");
+        assert_eq!(board.to_san(Move::new(Square::new(3, 3), Square::new(2, 2))), "Qdc3");
+    }
+
+    #[test]
+    fn san_errors() {
+        let board = Board::from_fen("6k1/8/8/8/R6R/8/8/R3K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.parse_san("Rd4").err().unwrap(), SanError::Ambiguous("Rd4".to_string()));
+        assert_eq!(board.parse_san("Nf3").err().unwrap(), SanError::Illegal("Nf3".to_string()));
+        assert_eq!(board.parse_san("O-O").err().unwrap(), SanError::Illegal("O-O".to_string()));
+        assert_eq!(board.parse_san("Rz9").err().unwrap(), SanError::Invalid("Rz9".to_string()));
+        assert_eq!(board.parse_san("").err().unwrap(), SanError::Invalid("".to_string()));
+        assert_eq!(board.parse_san("a8=X").err().unwrap(), SanError::Invalid("a8=X".to_string()));
+    }
@@ -0,0 +1,69 @@
+use chess_api::board::*;
+use chess_api::movement::*;
+use chess_api::piece::*;
+
+fn see(fen: &str, uci: &str) -> i32 {
+    let board = Board::from_fen(fen).unwrap();
+    let m: Move = uci.parse().unwrap();
+
+    assert!(board.check_move_possibility(m).is_ok(), "{} is not legal in {}", uci, fen);
+    board.see(m)
+}
+
+    #[test]
+    fn see_undefended_capture() {
+        assert_eq!(see("1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1", "e1e5"), 100);
+    }
+
+    #[test]
+    fn see_defended_capture() {
+        assert_eq!(see("4k3/8/3p4/4n3/3P4/8/8/4K3 w - - 0 1", "d4e5"), 220);
+        assert_eq!(see("4k3/8/3p4/4p3/8/8/8/4QK2 w - - 0 1", "e1e5"), -800);
+    }
+
+    #[test]
+    fn see_long_exchange_with_x_rays() {
+        // queen on h8 joins after bishop on f6, queen on e1 after rook on e2
+        assert_eq!(see("1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1", "d3e5"), -220);
+    }
+
+    #[test]
+    fn see_x_ray_behind_rook() {
+        assert_eq!(see("4k3/3n4/8/4p3/8/8/4R3/4R1K1 w - - 0 1", "e2e5"), -80);
+        assert_eq!(see("4k3/3n4/8/4p3/8/8/4R3/6K1 w - - 0 1", "e2e5"), -400);
+    }
+
+    #[test]
+    fn see_side_stops_capturing() {
+        // black would lose the rook by taking back, so it does not
+        assert_eq!(see("3rk3/8/8/3p4/2P5/8/3R4/3RK3 w - - 0 1", "c4d5"), 100);
+        assert_eq!(see("3rk3/8/8/3p4/2P5/8/8/4K3 w - - 0 1", "c4d5"), 0);
+    }
+
+    #[test]
+    fn see_king_captures_only_undefended() {
+        assert_eq!(see("8/8/8/8/8/4k3/3p4/2BR2K1 w - - 0 1", "d1d2"), 100);
+        assert_eq!(see("8/8/8/8/8/4k3/3p4/3R2K1 w - - 0 1", "d1d2"), -400);
+    }
+
+    #[test]
+    fn see_en_passant_and_promotion() {
+        assert_eq!(see("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), 100);
+        assert_eq!(see("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1", "b7b8q"), 800);
+        assert_eq!(see("r3k3/1P6/8/8/8/8/8/4K3 w - - 0 1", "b7a8q"), 1300);
+        assert_eq!(see("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8q"), -100);
+    }
+
+    #[test]
+    fn see_quiet_move() {
+        assert_eq!(see("4k3/8/8/8/8/8/8/4K1N1 w - - 0 1", "g1f3"), 0);
+        assert_eq!(see("4k3/8/8/8/4p3/8/8/4K1N1 w - - 0 1", "g1f3"), -320);
+    }
+
+    #[test]
+    fn see_custom_values() {
+        let board = Board::from_fen("4k3/8/3p4/4n3/3P4/8/8/4K3 w - - 0 1").unwrap();
+        let values = PieceValues { pawn: 1, knight: 3, bishop: 3, rook: 5, queen: 9, king: 100 };
+
+        assert_eq!(board.see_with_values("d4e5".parse().unwrap(), &values), 2);
+    }
@@ -0,0 +1,136 @@
+use chess_api::board::*;
+use chess_api::movement::*;
+use chess_api::pgn::*;
+use chess_api::tree::*;
+
+fn sans(tree: &GameTree, nodes: &[NodeId]) -> Vec<String> {
+    nodes.iter().map(|id| tree.node(*id).san().to_string()).collect()
+}
+
+    #[test]
+    fn repeated_move_is_not_added_twice() {
+        let mut tree = GameTree::new();
+
+        let e4 = tree.play_san("e4").unwrap();
+        tree.go_back();
+
+        assert_eq!(tree.play_san("e4").unwrap(), e4);
+        assert_eq!(tree.node(tree.root()).children(), [e4]);
+
+        assert!(tree.play_san("e4").is_err());
+        assert!(tree.play(Move::from_uci("e7e4").unwrap()).is_err());
+        assert_eq!(tree.current(), e4);
+    }
+
+    #[test]
+    fn variations_are_added_without_moving_board() {
+        let mut tree = GameTree::new();
+
+        let e4 = tree.play_san("e4").unwrap();
+        tree.play_san("e5").unwrap();
+        let nf3 = tree.play_san("Nf3").unwrap();
+
+        let d6 = tree.add_variation(e4, &["c5", "Nf3", "d6"]).unwrap();
+        let e6 = tree.add_variation(e4, &["e6"]).unwrap();
+
+        assert_eq!(tree.current(), nf3);
+        assert_eq!(tree.board().to_fen(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+        assert_eq!(sans(&tree, tree.node(e4).children()), ["e5", "c5", "e6"]);
+        assert_eq!(sans(&tree, &tree.path(d6)), ["e4", "c5", "Nf3", "d6"]);
+        assert_eq!(tree.node(e6).parent(), Some(e4));
+
+        // failed variation leaves tree unchanged, even when it shares moves with existing one
+        assert!(tree.add_variation(e4, &["c5", "Nc3", "Ke7"]).is_err());
+        assert_eq!(tree.node(tree.node(e4).children()[1]).children().len(), 1);
+        assert_eq!(tree.current(), nf3);
+    }
+
+    #[test]
+    fn walking_keeps_board_in_sync() {
+        let mut tree = GameTree::new();
+
+        for san in ["d4", "d5", "c4", "e6"] {
+            tree.play_san(san).unwrap();
+        }
+
+        let d4 = tree.mainline()[0];
+        let dxc4 = tree.add_variation(tree.mainline()[2], &["dxc4"]).unwrap();
+
+        tree.go_to(dxc4);
+        assert_eq!(tree.board().to_fen(), "rnbqkbnr/ppp1pppp/8/8/2pP4/8/PP2PPPP/RNBQKBNR w KQkq - 0 3");
+        assert_eq!(tree.to_game().moves().count(), 4);
+
+        assert!(tree.go_back());
+        assert!(tree.go_forward());
+        assert_eq!(sans(&tree, &[tree.current()]), ["e6"]);
+        assert!(!tree.go_forward());
+
+        tree.go_to(d4);
+        assert!(tree.go_back());
+        assert!(!tree.go_back());
+        assert_eq!(tree.board().to_fen(), Board::new().to_fen());
+    }
+
+    #[test]
+    fn promotion_makes_line_mainline() {
+        let mut tree = GameTree::new();
+
+        for san in ["e4", "e5", "Nf3", "Nc6"] {
+            tree.play_san(san).unwrap();
+        }
+
+        let e4 = tree.mainline()[0];
+        let nf6 = tree.add_variation(e4, &["c5", "Nf3", "d6"]).unwrap();
+        let nested = tree.add_variation(tree.node(nf6).parent().unwrap(), &["Nc6"]).unwrap();
+
+        tree.promote_variation(nested);
+        assert_eq!(sans(&tree, &tree.mainline()), ["e4", "e5", "Nf3", "Nc6"]);
+
+        tree.promote(nested);
+        assert_eq!(sans(&tree, &tree.mainline()), ["e4", "c5", "Nf3", "Nc6"]);
+        assert_eq!(sans(&tree, tree.node(e4).children()), ["c5", "e5"]);
+    }
+
+    #[test]
+    fn deleting_subtree_moves_board_out_of_it() {
+        let mut tree = GameTree::new();
+
+        let e4 = tree.play_san("e4").unwrap();
+        let c5 = tree.add_variation(e4, &["c5"]).unwrap();
+        let nf3 = tree.add_variation(c5, &["Nf3", "d6"]).unwrap();
+        tree.play_san("e5").unwrap();
+
+        tree.go_to(nf3);
+        tree.delete(c5);
+
+        assert_eq!(tree.current(), e4);
+        assert_eq!(tree.board().to_fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        assert_eq!(sans(&tree, tree.node(e4).children()), ["e5"]);
+        assert!(!tree.contains(c5) && !tree.contains(nf3));
+
+        tree.delete(tree.root());
+        assert!(tree.contains(tree.root()));
+    }
+
+    #[test]
+    fn tree_converts_from_and_to_pgn() {
+        let text = "[Event \"x\"]\n\n{Start} 1. e4 e5 (1... c5 2. Nf3 (2. Nc3 Nc6 (2... d6)) 2... d6) (1... e6 {French}) 2. Nf3 $1 Nc6 *";
+        let pgn: PgnGame = text.parse().unwrap();
+        let mut tree = GameTree::from(&pgn);
+
+        assert_eq!(tree.current(), tree.root());
+        assert_eq!(sans(&tree, &tree.mainline()), ["e4", "e5", "Nf3", "Nc6"]);
+
+        let e4 = tree.mainline()[0];
+        assert_eq!(tree.node(e4).comments_before, ["Start"]);
+        assert_eq!(sans(&tree, tree.node(e4).children()), ["e5", "c5", "e6"]);
+        assert_eq!(tree.node(tree.node(e4).children()[2]).comments, ["French"]);
+        assert_eq!(tree.node(tree.mainline()[2]).nags, [1]);
+
+        assert_eq!(tree.to_pgn(&pgn), pgn);
+
+        tree.node_mut(tree.mainline()[3]).comments.push("main line".to_string());
+        tree.promote(tree.node(e4).children()[2]);
+
+        assert_eq!(tree.to_pgn(&pgn).to_string(), "[Event \"x\"]\n\n{Start} 1. e4 e6 {French} (1... e5 2. Nf3 $1 Nc6 {main line}) (1... c5 2. Nf3\n(2. Nc3 Nc6 (2... d6)) 2... d6) *\n");
+    }
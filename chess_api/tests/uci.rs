@@ -0,0 +1,270 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command as Process, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+use chess_api::engine::*;
+use chess_api::movement::*;
+use chess_api::piece::*;
+use chess_api::uci::*;
+
+/// # Engine binary running in child process
+struct EngineProcess {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>
+}
+
+impl EngineProcess {
+    fn start() -> EngineProcess {
+        let mut child = Process::new(env!("CARGO_BIN_EXE_engine"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout: ChildStdout = child.stdout.take().unwrap();
+        let (sender, lines) = mpsc::channel();
+
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        EngineProcess { child, stdin, lines }
+    }
+
+    fn send(&mut self, command: &str) {
+        writeln!(self.stdin, "{}", command).unwrap();
+    }
+
+    /// # Returns lines up to and including the first one starting with given prefix
+    fn read_until(&self, prefix: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        loop {
+            let line = self.lines.recv_timeout(Duration::from_secs(30)).expect("engine did not answer");
+            let done = line.starts_with(prefix);
+
+            lines.push(line);
+
+            if done {
+                return lines;
+            }
+        }
+    }
+
+    fn quit(mut self) {
+        self.send("quit");
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+fn go(command: &str) -> GoOptions {
+    match command.parse() {
+        Ok(Command::Go(go)) => go,
+        other => panic!("{:?}", other)
+    }
+}
+
+    #[test]
+    fn parse_simple_commands() {
+        assert_eq!("uci".parse(), Ok(Command::Uci));
+        assert_eq!("  isready ".parse(), Ok(Command::IsReady));
+        assert_eq!("debug off".parse(), Ok(Command::Debug(false)));
+        assert_eq!("stop".parse(), Ok(Command::Stop));
+        assert_eq!("".parse::<Command>(), Err(CommandError::Empty));
+        assert_eq!("hello".parse::<Command>(), Err(CommandError::UnknownCommand("hello".to_string())));
+    }
+
+    #[test]
+    fn parse_position() {
+        let fen = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1";
+
+        assert_eq!(format!("position fen {} moves e2e4 e8d7", fen).parse(), Ok(Command::Position {
+            fen: Some(fen.to_string()),
+            moves: vec![Move::from_uci("e2e4").unwrap(), Move::from_uci("e8d7").unwrap()]
+        }));
+        assert_eq!("position startpos".parse(), Ok(Command::Position { fen: None, moves: vec![] }));
+        assert_eq!("position fen moves e2e4".parse::<Command>(), Err(CommandError::MissingValue("fen".to_string())));
+        assert!(matches!("position startpos moves e2e9".parse::<Command>(), Err(CommandError::InvalidMove(_))));
+    }
+
+    #[test]
+    fn parse_set_option() {
+        assert_eq!("setoption name Clear Hash".parse(), Ok(Command::SetOption { name: "Clear Hash".to_string(), value: None }));
+        assert_eq!("setoption name Skill Level value 3".parse(), Ok(Command::SetOption { name: "Skill Level".to_string(), value: Some("3".to_string()) }));
+        assert!("setoption value 3".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn parse_go() {
+        let options = go("go wtime 300000 btime -20 winc 2000 binc 2000 movestogo 40");
+
+        assert_eq!(options.white_time, Some(Duration::from_secs(300)));
+        assert_eq!(options.black_time, Some(Duration::ZERO));
+        assert_eq!(options.white_increment, Some(Duration::from_secs(2)));
+        assert_eq!(options.moves_to_go, Some(40));
+        assert_eq!(options.infinite, false);
+
+        assert_eq!(go("go infinite").infinite, true);
+        assert_eq!(go("go searchmoves e2e4 d2d4 depth 3").depth, Some(3));
+        assert_eq!("go depth".parse::<Command>(), Err(CommandError::MissingValue("depth".to_string())));
+        assert_eq!("go depth x".parse::<Command>(), Err(CommandError::InvalidValue("x".to_string())));
+    }
+
+    #[test]
+    fn go_limits() {
+        assert_eq!(go("go depth 5 nodes 1000").limits(PieceColor::WHITE), SearchLimits { depth: Some(5), nodes: Some(1000), time: None });
+        assert_eq!(go("go movetime 250 wtime 10000").limits(PieceColor::WHITE), SearchLimits::time(Duration::from_millis(250)));
+        assert_eq!(go("go mate 2").limits(PieceColor::BLACK), SearchLimits::depth(3));
+        assert_eq!(go("go infinite depth 3").limits(PieceColor::WHITE), SearchLimits::default());
+
+        // clock of side to move is used
+        assert_eq!(go("go wtime 1000 btime 90000").limits(PieceColor::BLACK), SearchLimits::time(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn format_info() {
+        let mate = Info { depth: Some(3), score: Some(Score::Mate(-2)), ..Info::default() };
+        assert_eq!(mate.to_string(), "info depth 3 score mate -2");
+
+        let string = Info { string: Some("hello world".to_string()), ..Info::default() };
+        assert_eq!(string.to_string(), "info string hello world");
+    }
+
+    #[test]
+    fn engine_handshake_and_search() {
+        let mut engine = EngineProcess::start();
+
+        engine.send("uci");
+        let lines = engine.read_until("uciok");
+        assert!(lines[0].starts_with("id name"));
+        assert!(lines.iter().any(|line| line.starts_with("option name Hash")));
+
+        engine.send("isready");
+        assert_eq!(engine.read_until("readyok"), ["readyok"]);
+
+        // white mates with Qd8
+        engine.send("ucinewgame");
+        engine.send("position fen 6k1/5ppp/8/8/8/8/5PPP/3Q2K1 w - - 0 1");
+        engine.send("go depth 3");
+
+        let lines = engine.read_until("bestmove");
+        assert_eq!(lines.last().unwrap(), "bestmove d1d8");
+        assert!(lines.iter().any(|line| line.starts_with("info depth 1 score mate 1 nodes") && line.ends_with("pv d1d8")));
+
+        engine.quit();
+    }
+
+    #[test]
+    fn engine_plays_moves_from_position() {
+        let mut engine = EngineProcess::start();
+
+        // after 1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 white mates with Qxf7
+        engine.send("position startpos moves e2e4 e7e5 d1h5 b8c6 f1c4 g8f6");
+        engine.send("go movetime 2000");
+        assert_eq!(engine.read_until("bestmove").last().unwrap(), "bestmove h5f7");
+
+        engine.quit();
+    }
+
+    #[test]
+    fn engine_writes_chess960_castling_as_king_taking_rook() {
+        let mut engine = EngineProcess::start();
+
+        engine.send("uci");
+        assert!(engine.read_until("uciok").contains(&"option name UCI_Chess960 type check default false".to_string()));
+
+        // only castling mates
+        engine.send("position fen 4rkr1/4p1p1/8/8/8/8/8/4K2R w K - 0 1");
+        engine.send("go depth 2");
+        assert_eq!(engine.read_until("bestmove").last().unwrap(), "bestmove e1g1");
+
+        engine.send("setoption name UCI_Chess960 value true");
+        engine.send("position fen 4rkr1/4p1p1/8/8/8/8/8/4K2R w K - 0 1");
+        engine.send("go depth 2");
+        assert_eq!(engine.read_until("bestmove").last().unwrap(), "bestmove e1h1");
+
+        engine.quit();
+    }
+
+    #[test]
+    fn engine_waits_for_stop_in_infinite_search() {
+        let mut engine = EngineProcess::start();
+
+        engine.send("position startpos");
+        engine.send("go infinite");
+        thread::sleep(Duration::from_millis(300));
+
+        engine.send("isready");
+        let lines = engine.read_until("readyok");
+        assert!(!lines.iter().any(|line| line.starts_with("bestmove")));
+
+        engine.send("stop");
+        let lines = engine.read_until("bestmove");
+        assert!(lines.last().unwrap().len() > "bestmove ".len());
+
+        engine.quit();
+    }
+
+    #[test]
+    fn ponder_hit_ends_ponder_search() {
+        let mut engine = EngineProcess::start();
+
+        engine.send("position startpos moves e2e4");
+        engine.send("go ponder");
+        thread::sleep(Duration::from_millis(100));
+
+        engine.send("isready");
+        assert!(!engine.read_until("readyok").iter().any(|line| line.starts_with("bestmove")));
+
+        engine.send("ponderhit");
+        assert!(engine.read_until("bestmove").last().unwrap().len() > "bestmove ".len());
+
+        engine.quit();
+    }
+
+    #[test]
+    fn parse_engine_messages() {
+        assert_eq!("id name Some Engine 2".parse(), Ok(EngineMessage::Id { name: "name".to_string(), value: "Some Engine 2".to_string() }));
+        assert_eq!("uciok".parse(), Ok(EngineMessage::UciOk));
+        assert_eq!("bestmove 0000".parse(), Ok(EngineMessage::BestMove { m: None, ponder: None }));
+        assert_eq!("bestmove a7a8q".parse(), Ok(EngineMessage::BestMove { m: Some(Move::from_uci("a7a8q").unwrap()), ponder: None }));
+        assert!(matches!("bestmove".parse::<EngineMessage>(), Err(CommandError::MissingValue(_))));
+        assert!(matches!("readyok now".parse::<EngineMessage>(), Ok(EngineMessage::ReadyOk)));
+    }
+
+    #[test]
+    fn parse_info() {
+        let info: Info = "info depth 12 seldepth 18 multipv 1 score cp -31 upperbound nodes 52000 nps 99000 hashfull 12 time 525 pv e7e5 g1f3 currmove e7e5 currmovenumber 1"
+            .parse().unwrap();
+
+        assert_eq!(info.depth, Some(12));
+        assert_eq!(info.score, Some(Score::Centipawns(-31)));
+        assert_eq!(info.nodes, Some(52000));
+        assert_eq!(info.time, Some(Duration::from_millis(525)));
+        assert_eq!(info.pv, [Move::from_uci("e7e5").unwrap(), Move::from_uci("g1f3").unwrap()]);
+
+        let info: Info = "info refutation d1h5 g6h5 depth 3 string searching deeper".parse().unwrap();
+        assert_eq!(info.depth, Some(3));
+        assert_eq!(info.string.as_deref(), Some("searching deeper"));
+
+        // formatted info is parsed back
+        let formatted = Info { depth: Some(4), score: Some(Score::Mate(2)), nodes: Some(10), time: Some(Duration::from_millis(7)), ..Info::default() };
+        assert_eq!(formatted.to_string().parse(), Ok(formatted));
+    }
+
+    #[test]
+    fn format_go() {
+        assert_eq!(GoOptions::from(SearchLimits::depth(7)).to_string(), "go depth 7");
+        assert_eq!(go("go btime 1000 wtime 2000 binc 10 movestogo 5 infinite").to_string(), "go wtime 2000 btime 1000 binc 10 movestogo 5 infinite");
+    }
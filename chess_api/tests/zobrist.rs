@@ -0,0 +1,89 @@
+use chess_api::board::*;
+use chess_api::movement::*;
+use chess_api::piece::*;
+
+    /// # Xorshift generator, good enough to pick random moves
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+    }
+
+    fn from_scratch(board: &Board) -> u64 {
+        Board::from_fen(&board.to_fen()).unwrap().hash()
+    }
+
+    #[test]
+    fn incremental_hash_matches_from_scratch() {
+        let mut random = Random(0x2545_F491_4F6C_DD1D);
+
+        for _ in 0..8 {
+            let mut board = Board::new();
+            let mut undos = vec![];
+
+            for _ in 0..60 {
+                let moves: Vec<Move> = board.all_possible_moves(Some(board.side_to_move())).collect();
+
+                if moves.is_empty() {
+                    break;
+                }
+
+                let hash = board.hash();
+                let (state, undo) = board.perform_move_with_undo(moves[random.next(moves.len())]).unwrap();
+
+                assert_eq!(board.hash(), from_scratch(&board), "{}", board.to_fen());
+                assert_ne!(board.hash(), hash);
+
+                undos.push((hash, undo));
+
+                if matches!(state, PostMoveState::Draw(_)) {
+                    break;
+                }
+            }
+
+            while let Some((hash, undo)) = undos.pop() {
+                board.undo_move(undo);
+                assert_eq!(board.hash(), hash);
+            }
+
+            assert_eq!(board.hash(), Board::new().hash());
+        }
+    }
+
+    #[test]
+    fn hash_after_set() {
+        let mut board = Board::new();
+
+        board.set(Square::new(7, 0), None);
+        assert_eq!(board.hash(), from_scratch(&board));
+
+        board.set(Square::new(4, 4), Some(Piece::new(PieceType::Queen, PieceColor::BLACK)));
+        assert_eq!(board.hash(), from_scratch(&board));
+
+        board.set_side_to_move(PieceColor::BLACK);
+        assert_eq!(board.hash(), from_scratch(&board));
+    }
+
+    #[test]
+    fn hash_covers_castling_and_en_passant() {
+        let with_castling = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let without_castling = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Kkq - 0 1").unwrap();
+
+        assert_ne!(with_castling.hash(), without_castling.hash());
+
+        let en_passant = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let no_en_passant = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1").unwrap();
+
+        assert_ne!(en_passant.hash(), no_en_passant.hash());
+
+        // nothing can capture en passant, so positions are same
+        let en_passant = Board::from_fen("4k3/8/8/3p4/8/8/8/4K3 w - d6 0 1").unwrap();
+        let no_en_passant = Board::from_fen("4k3/8/8/3p4/8/8/8/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(en_passant.hash(), no_en_passant.hash());
+    }
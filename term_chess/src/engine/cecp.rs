@@ -0,0 +1,256 @@
+use std::ffi::OsStr;
+use std::time::{Duration, Instant};
+
+use chess_api::board::Board;
+use chess_api::cecp::{parse_move, EngineMessage, Thinking};
+use chess_api::engine::SearchLimits;
+use chess_api::game::Game;
+use chess_api::movement::Move;
+
+use super::process::{EngineProcess, DEFAULT_MAX_SEARCH_TIME, DEFAULT_TIMEOUT};
+use super::EngineError;
+
+/// # Answer of engine to `go`
+#[derive(Debug, PartialEq, Clone)]
+pub enum EngineReply {
+    Move(Move),
+    Resign,
+    /// engine claims the game has ended, e.g. `1/2-1/2 {Draw by repetition}`
+    Result { result: String, comment: Option<String> }
+}
+
+/// # Engine speaking Chess Engine Communication Protocol, running in separate process
+///
+/// engine is kept in force mode, so it only thinks after `go`,
+/// engine is quit when dropped
+///
+pub struct CecpEngine {
+    process: EngineProcess,
+    features: Vec<(String, String)>,
+    pings: u32,
+    max_search_time: Duration
+}
+
+impl CecpEngine {
+    /// # Starts engine program and waits for its features
+    ///
+    /// engines which do not send features in time are treated as protocol version 1
+    ///
+    pub fn start<S: AsRef<OsStr>>(program: S, args: &[S]) -> Result<CecpEngine, EngineError> {
+        CecpEngine::start_with_timeout(program, args, DEFAULT_TIMEOUT)
+    }
+
+    /// # Starts engine which has given time to answer every command, see `set_timeout`
+    pub fn start_with_timeout<S: AsRef<OsStr>>(program: S, args: &[S], timeout: Duration) -> Result<CecpEngine, EngineError> {
+        let mut engine = CecpEngine {
+            process: EngineProcess::spawn(program, args, timeout)?,
+            features: Vec::new(),
+            pings: 0,
+            max_search_time: DEFAULT_MAX_SEARCH_TIME
+        };
+
+        engine.process.send("xboard")?;
+        engine.process.send("protover 2")?;
+
+        let deadline = engine.process.deadline();
+
+        loop {
+            let features = match engine.process.receive(Some(deadline), "feature done=1") {
+                Ok(EngineMessage::Feature(features)) => features,
+                Ok(_) => continue,
+                Err(EngineError::Timeout(_)) => break,
+                Err(e) => return Err(e)
+            };
+
+            for (name, value) in features {
+                engine.process.send(&format!("accepted {}", name))?;
+                engine.features.push((name, value));
+            }
+
+            if engine.feature("done") == Some("1") {
+                break;
+            }
+        }
+
+        engine.new_game()?;
+        Ok(engine)
+    }
+
+    /// # Returns value of feature sent by engine, the last one when sent more times
+    pub fn feature(&self, name: &str) -> Option<&str> {
+        self.features.iter().rev().find(|(feature, _)| feature == name).map(|(_, value)| value.as_str())
+    }
+
+    /// # Returns name engine introduced itself with
+    pub fn name(&self) -> Option<&str> {
+        self.feature("myname")
+    }
+
+    /// # Sets how long engine can take to answer, on top of time given for search
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.process.timeout = timeout;
+    }
+
+    /// # Sets how long search without time limit can take before engine is told to move, see `search`
+    pub fn set_max_search_time(&mut self, time: Duration) {
+        self.max_search_time = time;
+    }
+
+    /// # Waits until engine finishes processing previous commands
+    ///
+    /// engines without ping feature are not waited for
+    ///
+    pub fn ping(&mut self) -> Result<(), EngineError> {
+        if self.feature("ping") != Some("1") {
+            return Ok(());
+        }
+
+        self.pings += 1;
+        self.process.send(&format!("ping {}", self.pings))?;
+
+        let deadline = self.process.deadline();
+
+        while self.process.receive::<EngineMessage>(Some(deadline), "pong")? != EngineMessage::Pong(self.pings) {}
+
+        Ok(())
+    }
+
+    /// # Tells engine to start new game from standard position
+    pub fn new_game(&mut self) -> Result<(), EngineError> {
+        self.process.send("new")?;
+        self.process.send("force")?;
+        self.process.send("post")
+    }
+
+    /// # Sets up position of given game, starting from its first position and sending every move
+    pub fn set_game(&mut self, game: &Game) -> Result<(), EngineError> {
+        self.new_game()?;
+
+        let mut board = game.start_board();
+
+        if board.to_fen() != Board::new().to_fen() {
+            self.set_board(&board)?;
+        }
+
+        for game_move in game.moves() {
+            self.user_move(&board, game_move.get_move())?;
+            board.apply_move(game_move.get_move());
+        }
+
+        Ok(())
+    }
+
+    /// # Sets position, engine forgets moves played before
+    pub fn set_board(&mut self, board: &Board) -> Result<(), EngineError> {
+        if self.feature("setboard") != Some("1") {
+            return Err(EngineError::Rejected("setboard".to_string()));
+        }
+
+        self.process.send(&format!("setboard {}", board.to_fen()))
+    }
+
+    /// # Sends move played in given position, in SAN when engine asked for it
+    pub fn user_move(&mut self, board: &Board, m: Move) -> Result<(), EngineError> {
+        let text = match self.feature("san") {
+            Some("1") => board.to_san(m),
+            _ => m.to_uci()
+        };
+
+        match self.feature("usermove") {
+            Some("1") => self.process.send(&format!("usermove {}", text)),
+            _ => self.process.send(&text)
+        }
+    }
+
+    /// # Sends remaining time of engine and its opponent
+    pub fn set_time(&mut self, engine: Duration, opponent: Duration) -> Result<(), EngineError> {
+        self.process.send(&format!("time {}", engine.as_millis() / 10))?;
+        self.process.send(&format!("otim {}", opponent.as_millis() / 10))
+    }
+
+    /// # Sets time control, `moves` moves in `base` time or whole game in `base` time when `moves` is 0
+    pub fn set_level(&mut self, moves: u32, base: Duration, increment: Duration) -> Result<(), EngineError> {
+        let seconds = base.as_secs();
+        self.process.send(&format!("level {} {}:{:02} {}", moves, seconds / 60, seconds % 60, increment.as_secs_f64()))
+    }
+
+    /// # Limits time of every move, whole seconds are sent
+    pub fn set_move_time(&mut self, time: Duration) -> Result<(), EngineError> {
+        self.process.send(&format!("st {}", time.as_secs().max(1)))
+    }
+
+    pub fn set_depth(&mut self, depth: u32) -> Result<(), EngineError> {
+        self.process.send(&format!("sd {}", depth))
+    }
+
+    /// # Lets engine play side to move in given position, calling `thinking` for every thinking line
+    ///
+    /// engine is told to move immediately when it does not move in given time,
+    /// then it has only time given to answer
+    ///
+    pub fn go<F: FnMut(&Thinking)>(&mut self, board: &Board, time: Option<Duration>, mut thinking: F) -> Result<EngineReply, EngineError> {
+        self.process.send("go")?;
+
+        let mut deadline = time.map(|time| Instant::now() + time);
+        let mut hurried = false;
+
+        let reply = loop {
+            let message = match self.process.receive(deadline, "move") {
+                Err(EngineError::Timeout(_)) if !hurried => {
+                    self.process.send("?")?;
+                    hurried = true;
+                    deadline = Some(self.process.deadline());
+                    continue;
+                },
+                message => message?
+            };
+
+            match message {
+                EngineMessage::Thinking(line) => thinking(&line),
+                EngineMessage::Move(text) => match parse_move(board, &text) {
+                    Ok(m) => break EngineReply::Move(m),
+                    Err(_) => return Err(EngineError::IllegalMove(text))
+                },
+                EngineMessage::Resign => break EngineReply::Resign,
+                EngineMessage::Result { result, comment } => break EngineReply::Result { result, comment },
+                EngineMessage::IllegalMove(text) => return Err(EngineError::Rejected(text)),
+                _ => ()
+            }
+        };
+
+        // engine would otherwise answer next move by itself
+        self.process.send("force")?;
+
+        Ok(reply)
+    }
+
+    /// # Searches current position of given game within given limits
+    ///
+    /// search without time limit is hurried after maximum search time, see `set_max_search_time`
+    ///
+    pub fn search<F: FnMut(&Thinking)>(&mut self, game: &Game, limits: SearchLimits, thinking: F) -> Result<EngineReply, EngineError> {
+        self.set_game(game)?;
+
+        if let Some(time) = limits.time {
+            self.set_move_time(time)?;
+        }
+
+        if let Some(depth) = limits.depth {
+            self.set_depth(depth)?;
+        }
+
+        let time = limits.time.unwrap_or(self.max_search_time);
+        let timeout = self.process.timeout;
+        self.go(game.board(), Some(time + timeout), thinking)
+    }
+
+    /// # Tells engine how the game ended
+    pub fn result(&mut self, result: &str, comment: &str) -> Result<(), EngineError> {
+        self.process.send(&format!("result {} {{{}}}", result, comment))
+    }
+
+    /// # Asks engine to quit, it is killed when it does not exit in time given to answer
+    pub fn quit(mut self) -> Result<(), EngineError> {
+        self.process.shutdown()
+    }
+}
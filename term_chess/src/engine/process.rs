@@ -0,0 +1,144 @@
+use std::ffi::OsStr;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// how long engine can take to answer a command
+pub(super) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// longest search without time limit, e.g. search of given depth
+pub const DEFAULT_MAX_SEARCH_TIME: Duration = Duration::from_secs(60);
+
+/// # Reason why communication with engine failed
+#[derive(Debug)]
+pub enum EngineError {
+    Spawn(io::Error),
+    Io(io::Error),
+    /// engine did not answer in time, contains expected answer
+    Timeout(String),
+    /// engine process ended or closed its output
+    Exited,
+    /// engine refused command or does not support it
+    Rejected(String),
+    /// engine sent move which is not legal
+    IllegalMove(String)
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EngineError::Spawn(e) => write!(f, "engine could not be started: {}", e),
+            EngineError::Io(e) => write!(f, "communication with engine failed: {}", e),
+            EngineError::Timeout(expected) => write!(f, "engine did not send '{}' in time", expected),
+            EngineError::Exited => write!(f, "engine exited"),
+            EngineError::Rejected(command) => write!(f, "engine rejected '{}'", command),
+            EngineError::IllegalMove(m) => write!(f, "engine played illegal move '{}'", m)
+        }
+    }
+}
+
+impl std::error::Error for EngineError {}
+
+/// # Engine program talking over its standard input and output
+///
+/// engine is asked to quit when dropped and killed when it does not
+///
+pub(super) struct EngineProcess {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+    /// how long engine can take to answer a command
+    pub timeout: Duration
+}
+
+impl EngineProcess {
+    pub fn spawn<S: AsRef<OsStr>>(program: S, args: &[S], timeout: Duration) -> Result<EngineProcess, EngineError> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(EngineError::Spawn)?;
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, lines) = mpsc::channel();
+
+        // reading thread ends together with engine's output
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(EngineProcess { child, stdin, lines, timeout })
+    }
+
+    /// # Returns time after which engine is considered unresponsive
+    pub fn deadline(&self) -> Instant {
+        Instant::now() + self.timeout
+    }
+
+    pub fn send(&mut self, command: &str) -> Result<(), EngineError> {
+        writeln!(self.stdin, "{}", command)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::BrokenPipe => EngineError::Exited,
+                _ => EngineError::Io(e)
+            })
+    }
+
+    /// # Returns next message engine sends before deadline, lines which are not valid messages are skipped
+    pub fn receive<T: FromStr>(&mut self, deadline: Option<Instant>, expected: &str) -> Result<T, EngineError> {
+        loop {
+            let line = match deadline {
+                Some(deadline) => self.lines.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+                None => self.lines.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            };
+
+            match line {
+                Ok(line) => if let Ok(message) = line.parse() {
+                    return Ok(message);
+                },
+                Err(RecvTimeoutError::Timeout) => return Err(EngineError::Timeout(expected.to_string())),
+                Err(RecvTimeoutError::Disconnected) => return Err(EngineError::Exited)
+            }
+        }
+    }
+
+    /// # Asks engine to quit, it is killed when it does not exit in time given to answer
+    pub fn shutdown(&mut self) -> Result<(), EngineError> {
+        // engine may have already exited, then there is nobody to tell
+        let _ = self.send("quit");
+
+        let deadline = self.deadline();
+
+        while Instant::now() < deadline {
+            if self.child.try_wait().map_err(EngineError::Io)?.is_some() {
+                return Ok(());
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        self.child.kill().map_err(EngineError::Io)?;
+        self.child.wait().map_err(EngineError::Io)?;
+
+        Err(EngineError::Timeout("quit".to_string()))
+    }
+}
+
+impl Drop for EngineProcess {
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.shutdown();
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::ffi::OsStr;
+use std::time::{Duration, Instant};
+
+use chess_api::board::Board;
+use chess_api::engine::SearchLimits;
+use chess_api::game::Game;
+use chess_api::movement::Move;
+use chess_api::uci::{EngineMessage, GoOptions, Info};
+
+use super::process::{EngineProcess, DEFAULT_MAX_SEARCH_TIME, DEFAULT_TIMEOUT};
+use super::EngineError;
+
+/// # Best move sent by engine at the end of search
+#[derive(Debug, PartialEq, Clone)]
+pub struct EngineMove {
+    /// None when engine has no legal move
+    pub m: Option<Move>,
+    /// reply engine expects
+    pub ponder: Option<Move>,
+    /// last info line with principal variation
+    pub info: Option<Info>
+}
+
+/// # UCI engine running in separate process
+///
+/// engine is quit when dropped
+///
+pub struct UciEngine {
+    process: EngineProcess,
+    name: Option<String>,
+    author: Option<String>,
+    options: Vec<String>,
+    max_search_time: Duration
+}
+
+impl UciEngine {
+    /// # Starts engine program and waits for the end of UCI handshake
+    pub fn start<S: AsRef<OsStr>>(program: S, args: &[S]) -> Result<UciEngine, EngineError> {
+        UciEngine::start_with_timeout(program, args, DEFAULT_TIMEOUT)
+    }
+
+    /// # Starts engine which has given time to answer every command, see `set_timeout`
+    pub fn start_with_timeout<S: AsRef<OsStr>>(program: S, args: &[S], timeout: Duration) -> Result<UciEngine, EngineError> {
+        let mut engine = UciEngine {
+            process: EngineProcess::spawn(program, args, timeout)?,
+            name: None,
+            author: None,
+            options: Vec::new(),
+            max_search_time: DEFAULT_MAX_SEARCH_TIME
+        };
+
+        engine.process.send("uci")?;
+
+        let deadline = engine.process.deadline();
+
+        loop {
+            match engine.process.receive(Some(deadline), "uciok")? {
+                EngineMessage::Id { name, value } if name == "name" => engine.name = Some(value),
+                EngineMessage::Id { name, value } if name == "author" => engine.author = Some(value),
+                EngineMessage::Option(option) => engine.options.push(option),
+                EngineMessage::UciOk => return Ok(engine),
+                _ => ()
+            }
+        }
+    }
+
+    /// # Returns name engine introduced itself with
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// # Returns descriptions of options sent during handshake, e.g. `name Hash type spin default 16 min 1 max 1024`
+    pub fn options(&self) -> &[String] {
+        &self.options
+    }
+
+    /// # Sets how long engine can take to answer, on top of time given for search
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.process.timeout = timeout;
+    }
+
+    /// # Sets how long search without time limit can take before engine is stopped, see `search`
+    pub fn set_max_search_time(&mut self, time: Duration) {
+        self.max_search_time = time;
+    }
+
+    pub fn set_option(&mut self, name: &str, value: Option<&str>) -> Result<(), EngineError> {
+        match value {
+            Some(value) => self.process.send(&format!("setoption name {} value {}", name, value)),
+            None => self.process.send(&format!("setoption name {}", name))
+        }
+    }
+
+    /// # Waits until engine finishes processing previous commands
+    pub fn is_ready(&mut self) -> Result<(), EngineError> {
+        self.process.send("isready")?;
+
+        let deadline = self.process.deadline();
+
+        while self.process.receive::<EngineMessage>(Some(deadline), "readyok")? != EngineMessage::ReadyOk {}
+
+        Ok(())
+    }
+
+    /// # Tells engine that next position is from different game
+    pub fn new_game(&mut self) -> Result<(), EngineError> {
+        self.process.send("ucinewgame")?;
+        self.is_ready()
+    }
+
+    /// # Sends position as FEN of starting position followed by played moves
+    pub fn set_position(&mut self, start: &Board, moves: &[Move]) -> Result<(), EngineError> {
+        let mut command = format!("position fen {}", start.to_fen());
+
+        if !moves.is_empty() {
+            command.push_str(" moves");
+
+            for m in moves {
+                command.push_str(&format!(" {}", m));
+            }
+        }
+
+        self.process.send(&command)
+    }
+
+    /// # Sends current position of given game, so that engine knows about repetitions
+    pub fn set_game(&mut self, game: &Game) -> Result<(), EngineError> {
+        let moves: Vec<Move> = game.moves().map(|m| m.get_move()).collect();
+        self.set_position(&game.start_board(), &moves)
+    }
+
+    /// # Starts search of position set before, `wait_best_move` returns its result
+    pub fn go(&mut self, options: &GoOptions) -> Result<(), EngineError> {
+        self.process.send(&options.to_string())
+    }
+
+    /// # Tells engine to send best move found so far
+    pub fn stop(&mut self) -> Result<(), EngineError> {
+        self.process.send("stop")
+    }
+
+    /// # Waits for the end of search, calling `info` for every info line
+    ///
+    /// when best move is not sent in given time, engine is stopped
+    /// and then it has only time given to answer,
+    /// best move is not checked, as position is not known here
+    ///
+    pub fn wait_best_move<F: FnMut(&Info)>(&mut self, time: Option<Duration>, mut info: F) -> Result<EngineMove, EngineError> {
+        let mut deadline = time.map(|time| Instant::now() + time);
+        let mut stopped = false;
+        let mut last_info = None;
+
+        loop {
+            let message = match self.process.receive(deadline, "bestmove") {
+                Err(EngineError::Timeout(_)) if !stopped => {
+                    self.stop()?;
+                    stopped = true;
+                    deadline = Some(self.process.deadline());
+                    continue;
+                },
+                message => message?
+            };
+
+            match message {
+                EngineMessage::Info(line) => {
+                    info(&line);
+
+                    if !line.pv.is_empty() {
+                        last_info = Some(line);
+                    }
+                },
+                EngineMessage::BestMove { m, ponder } => return Ok(EngineMove { m, ponder, info: last_info }),
+                _ => ()
+            }
+        }
+    }
+
+    /// # Searches current position of given game within given limits
+    ///
+    /// engine is stopped when it exceeds time limit by more than time given to answer,
+    /// search without time limit is stopped after maximum search time, see `set_max_search_time`
+    ///
+    pub fn search<F: FnMut(&Info)>(&mut self, game: &Game, limits: SearchLimits, info: F) -> Result<EngineMove, EngineError> {
+        self.set_game(game)?;
+        self.go(&GoOptions::from(limits))?;
+
+        let time = limits.time.unwrap_or(self.max_search_time);
+        let best = self.wait_best_move(Some(time + self.process.timeout), info)?;
+
+        match best.m {
+            Some(m) if game.board().check_move_possibility(m).is_err() => Err(EngineError::IllegalMove(m.to_string())),
+            _ => Ok(best)
+        }
+    }
+
+    /// # Asks engine to quit, it is killed when it does not exit in time given to answer
+    pub fn quit(mut self) -> Result<(), EngineError> {
+        self.process.shutdown()
+    }
+}
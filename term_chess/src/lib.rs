@@ -1,11 +1,20 @@
 use std::io;
-use std::io::{ stdin, stdout, Read, Write };
+use std::io::Write;
 
 pub mod menu {
     pub mod main_menu;
     mod quick_pairing_menu;
     mod friend_menu;
     mod computer_menu;
+    mod computer_game;
+}
+
+pub mod engine {
+    mod process;
+    pub mod uci;
+    pub mod cecp;
+
+    pub use process::{EngineError, DEFAULT_MAX_SEARCH_TIME};
 }
 
 pub use crate::menu::main_menu;
@@ -36,6 +45,15 @@ impl Input {
     }
 
 
+    fn wait_for_enter() {
+        let mut line = String::new();
+
+        print!("\nPress enter to continue ");
+        io::stdout().flush().unwrap();
+
+        std::io::stdin().read_line(&mut line).expect("Error: Failed to take standard input!"); 
+    }
+
     fn proceed() -> bool {
         let mut line = String::new();
 
@@ -0,0 +1,262 @@
+use {std::io, std::io::*};
+use std::fs::OpenOptions;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chess_api::board::{Board, DrawReason, PostMoveState};
+use chess_api::engine::{Engine, SearchLimits, Strength};
+use chess_api::game::Game;
+use chess_api::movement::{Move, Square};
+use chess_api::pgn::PgnGame;
+use chess_api::piece::{Piece, PieceColor};
+
+use crate::{Menu, *};
+use super::main_menu::MainMenu;
+
+pub struct ComputerGame {
+    game: Game,
+    engine: Engine,
+    strength: u32,
+    player: PieceColor,
+    resigned: bool,
+    draw_claimed: Option<DrawReason>,
+    message: String,
+}
+
+impl Menu for ComputerGame {
+    fn display(&mut self) -> Option<Box<dyn Menu>> {
+        loop {
+            clean_screen();
+            self.print();
+
+            if let Some((result, description)) = self.result() {
+                println!("\n  {}", description);
+                self.game_over(result);
+
+                return Some(Box::new(MainMenu::new()));
+            }
+
+            if self.game.board().side_to_move() == self.player {
+                self.player_move();
+            } else {
+                self.computer_move();
+            }
+        }
+    }
+}
+
+impl ComputerGame {
+    pub fn new(strength: u32, player: PieceColor) -> ComputerGame {
+        let mut engine = Engine::new();
+        engine.set_strength(Strength::level(strength));
+
+        ComputerGame {
+            game: Game::new(),
+            engine,
+            strength,
+            player,
+            resigned: false,
+            draw_claimed: None,
+            message: String::new(),
+        }
+    }
+
+    fn print(&self) {
+        println!("Game with computer (strength {}):\n", self.strength);
+        print_board(self.game.board(), self.player);
+
+        if let Some(last) = self.game.last_move() {
+            println!("\n  Last move: {}", last.san());
+        }
+
+        if !self.message.is_empty() {
+            println!("  {}", self.message);
+        }
+    }
+
+    fn player_move(&mut self) {
+        print!("\n  Your move (SAN or UCI, \"undo\", \"draw\", \"resign\"): ");
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line).expect("Error: Failed to take standard input!");
+        let line = line.trim();
+
+        self.message.clear();
+
+        match line {
+            "resign" => self.resigned = true,
+            "undo" => {
+                self.game.undo_move();
+                if self.game.board().side_to_move() != self.player {
+                    self.game.undo_move();
+                }
+            },
+            "draw" => match self.game.claimable_draw() {
+                Some(reason) => self.draw_claimed = Some(reason),
+                None => self.message = String::from("Draw can not be claimed"),
+            },
+            _ => {
+                let played = match Move::from_uci(line) {
+                    Ok(m) => self.game.play(m).map_err(|e| format!("{:?}", e)),
+                    Err(_) => self.game.play_san(line).map_err(|e| e.to_string()),
+                };
+
+                if let Err(e) = played {
+                    self.message = format!("Invalid move: {}", e);
+                }
+            },
+        }
+    }
+
+    fn computer_move(&mut self) {
+        println!("\n  Computer is thinking...");
+
+        // strength decides depth, time limit only keeps the strongest level responsive
+        let limits = SearchLimits::time(Duration::from_secs(5));
+
+        if let Some(m) = self.engine.search_game(&self.game, limits).get_move() {
+            self.game.play(m).expect("Error: Engine returned illegal move!");
+        }
+    }
+
+    /// Returns result in PGN notation with its description when the game has ended
+    fn result(&self) -> Option<(&'static str, String)> {
+        if self.resigned {
+            return match self.player {
+                PieceColor::WHITE => Some(("0-1", String::from("White resigned, Black wins"))),
+                PieceColor::BLACK => Some(("1-0", String::from("Black resigned, White wins"))),
+            };
+        }
+
+        if let Some(reason) = self.draw_claimed {
+            return Some(("1/2-1/2", format!("Draw claimed: {:?}", reason)));
+        }
+
+        let (result, winner) = match self.game.board().side_to_move() {
+            PieceColor::WHITE => ("0-1", "Black"),
+            PieceColor::BLACK => ("1-0", "White"),
+        };
+
+        match self.game.last_move()?.state() {
+            PostMoveState::Checkmate => Some((result, format!("Checkmate, {} wins", winner))),
+            PostMoveState::Stelemate => Some(("1/2-1/2", String::from("Draw by stalemate"))),
+            PostMoveState::Draw(reason) => Some(("1/2-1/2", format!("Draw: {:?}", reason))),
+            PostMoveState::Normal | PostMoveState::Check => None,
+        }
+    }
+
+    fn game_over(&self, result: &str) {
+        let mut choice = 0;
+
+        while choice == 0 {
+            print!("\n  1. Save game\n  2. Back to main menu\n  ");
+            io::stdout().flush().unwrap();
+
+            choice = Input::one_to_(2);
+        }
+
+        if choice == 1 {
+            self.save(result);
+            Input::wait_for_enter();
+        }
+    }
+
+    /// Appends the game to PGN file chosen by player
+    fn save(&self, result: &str) {
+        print!("\n  File name [game.pgn]: ");
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line).expect("Error: Failed to take standard input!");
+
+        let path = match line.trim() {
+            "" => "game.pgn",
+            path => path,
+        };
+
+        let computer = format!("Computer (strength {})", self.strength);
+        let (white, black) = match self.player {
+            PieceColor::WHITE => ("Player", computer.as_str()),
+            PieceColor::BLACK => (computer.as_str(), "Player"),
+        };
+
+        let mut pgn = PgnGame::from_game(&self.game);
+        pgn.set_tag("Event", "Game with computer");
+        pgn.set_tag("Site", "term_chess");
+        pgn.set_tag("Date", &today());
+        pgn.set_tag("White", white);
+        pgn.set_tag("Black", black);
+        pgn.set_tag("TimeControl", "-");
+        pgn.set_result(result);
+
+        // games are separated by empty line, so that one file can hold more of them
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", pgn));
+
+        match written {
+            Ok(()) => println!("  Game saved to {}", path),
+            Err(e) => println!("  Error: Game could not be saved: {}", e),
+        }
+    }
+}
+
+/// Returns current date in PGN format, YYYY.MM.DD
+fn today() -> String {
+    let days = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() / 86400).unwrap_or(0) as i64;
+
+    // civil date from days since 1970-01-01, years start in March so that leap day is the last one
+    let days = days + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{}.{:02}.{:02}", year, month, day)
+}
+
+fn piece_char(piece: &Piece) -> char {
+    let c = piece.piece_type().to_uci();
+
+    match piece.color() {
+        PieceColor::WHITE => c.to_ascii_uppercase(),
+        PieceColor::BLACK => c,
+    }
+}
+
+/// Prints board as seen by player of given color
+fn print_board(board: &Board, side: PieceColor) {
+    let ranks: Vec<u8> = match side {
+        PieceColor::WHITE => (0..8).rev().collect(),
+        PieceColor::BLACK => (0..8).collect(),
+    };
+    let files: Vec<u8> = match side {
+        PieceColor::WHITE => (0..8).collect(),
+        PieceColor::BLACK => (0..8).rev().collect(),
+    };
+
+    for y in ranks {
+        print!("  {} ", y + 1);
+
+        for x in files.iter() {
+            match board.get_piece(Square::new(*x, y)) {
+                Some(piece) => print!(" {}", piece_char(piece)),
+                None => print!(" ."),
+            }
+        }
+
+        println!();
+    }
+
+    print!("\n    ");
+    for x in files {
+        print!(" {}", (b'a' + x) as char);
+    }
+    println!();
+}
@@ -1,5 +1,10 @@
 use {std::io, std::io::*};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chess_api::piece::PieceColor;
+
 use crate::{Menu, *};
+use super::computer_game::ComputerGame;
 
 pub struct ComputerMenu {
     strength: u32,
@@ -20,7 +25,7 @@ impl Menu for ComputerMenu {
             return Some(Box::new(ComputerMenu::new()));
         }
     
-        None
+        Some(Box::new(ComputerGame::new(self.strength, self.player_color())))
     }
 }
 
@@ -79,6 +84,17 @@ impl ComputerMenu {
         println!("      3. Random");
     }
 
+    fn player_color(&self) -> PieceColor {
+        match self.side {
+            1 => PieceColor::WHITE,
+            2 => PieceColor::BLACK,
+            _ => {
+                let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos()).unwrap_or(0);
+                if nanos.is_multiple_of(2) { PieceColor::WHITE } else { PieceColor::BLACK }
+            },
+        }
+    }
+
     fn format_side_choice(&self) {
         match self.side {
             1 => println!("  Side: White"),
@@ -43,7 +43,8 @@ impl MainMenu {
 
 
 
-#[cfg(test)]
-mod test {
-    use super::*;
+impl Default for MainMenu {
+    fn default() -> Self {
+        MainMenu::new()
+    }
 }
@@ -0,0 +1,130 @@
+use std::time::{Duration, Instant};
+
+use chess_api::board::Board;
+use chess_api::engine::SearchLimits;
+use chess_api::game::Game;
+use chess_api::movement::Move;
+use term_chess::engine::cecp::{CecpEngine, EngineReply};
+use term_chess::engine::EngineError;
+
+const FAKE_ENGINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fake_cecp_engine.sh");
+
+fn start(mode: &str) -> CecpEngine {
+    CecpEngine::start_with_timeout("sh", &[FAKE_ENGINE, mode], Duration::from_millis(500)).unwrap()
+}
+
+fn uci(m: &str) -> Move {
+    Move::from_uci(m).unwrap()
+}
+
+    #[test]
+    fn features_are_collected() {
+        let mut engine = start("");
+
+        assert_eq!(engine.name(), Some("Fake CECP"));
+        assert_eq!(engine.feature("usermove"), Some("1"));
+        assert_eq!(engine.feature("ping"), Some("1"));
+        assert_eq!(engine.feature("san"), None);
+
+        assert!(engine.ping().is_ok());
+        assert!(engine.quit().is_ok());
+    }
+
+    #[test]
+    fn search_sends_moves_and_limits() {
+        let mut engine = start("");
+        let mut game = Game::new();
+        game.play_san("e4").unwrap();
+
+        let mut thinking = Vec::new();
+        let reply = engine.search(&game, SearchLimits { depth: Some(4), time: Some(Duration::from_secs(2)), nodes: None }, |line| thinking.push(line.clone())).unwrap();
+
+        assert_eq!(reply, EngineReply::Move(uci("e7e5")));
+        assert_eq!(thinking[0].pv, "moves: e2e4");
+        assert_eq!(thinking[1].pv, "limits: st 2 sd 4");
+        assert_eq!(thinking[1].depth, 2);
+        assert_eq!(thinking[1].score, 25);
+        assert_eq!(thinking[1].time, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn custom_position_is_set_with_setboard() {
+        let mut engine = start("");
+        let fen = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1";
+
+        let mut thinking = Vec::new();
+        let reply = engine.search(&Game::from_board(Board::from_fen(fen).unwrap()), SearchLimits::default(), |line| thinking.push(line.pv.clone()));
+
+        assert_eq!(reply.unwrap(), EngineReply::Move(uci("e2e4")));
+        assert_eq!(thinking[0], format!("moves:[{}]", fen));
+    }
+
+    #[test]
+    fn moves_are_sent_in_san_when_asked() {
+        let mut engine = start("san");
+        let mut game = Game::new();
+        game.play_san("Nf3").unwrap();
+
+        let mut thinking = Vec::new();
+        let reply = engine.search(&game, SearchLimits::default(), |line| thinking.push(line.pv.clone())).unwrap();
+
+        assert_eq!(reply, EngineReply::Move(uci("b8c6")));
+        assert_eq!(thinking[0], "moves: Nf3");
+    }
+
+    #[test]
+    fn protocol_version_1_engine_gets_plain_moves() {
+        let started = Instant::now();
+        let mut engine = start("v1");
+
+        // features were waited for until timeout
+        assert!(started.elapsed() >= Duration::from_millis(500));
+        assert_eq!(engine.name(), None);
+
+        let mut game = Game::new();
+        game.play_san("d4").unwrap();
+
+        let mut thinking = Vec::new();
+        engine.set_game(&game).unwrap();
+        engine.set_time(Duration::from_secs(60), Duration::from_secs(30)).unwrap();
+        engine.set_level(40, Duration::from_secs(300), Duration::ZERO).unwrap();
+
+        let reply = engine.go(game.board(), None, |line| thinking.push(line.pv.clone())).unwrap();
+
+        assert_eq!(reply, EngineReply::Move(uci("e7e5")));
+        assert_eq!(thinking, ["moves: d2d4", "limits: time 6000 otim 3000 level 40 5:00 0"]);
+        assert!(matches!(engine.set_board(&Board::new()), Err(EngineError::Rejected(_))));
+    }
+
+    #[test]
+    fn slow_engine_is_hurried() {
+        let mut engine = start("slow");
+
+        let started = Instant::now();
+        let reply = engine.go(&Board::new(), Some(Duration::from_millis(200)), |_| ()).unwrap();
+
+        assert_eq!(reply, EngineReply::Move(uci("d2d4")));
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn slow_engine_without_time_limit_is_hurried() {
+        let mut engine = start("slow");
+        engine.set_max_search_time(Duration::from_millis(200));
+
+        let started = Instant::now();
+        let reply = engine.search(&Game::new(), SearchLimits::depth(4), |_| ()).unwrap();
+
+        assert_eq!(reply, EngineReply::Move(uci("d2d4")));
+        assert!(started.elapsed() >= Duration::from_millis(700));
+    }
+
+    #[test]
+    fn illegal_engine_move_is_reported() {
+        let mut engine = start("");
+
+        // e2e4 is not possible without the pawn
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+
+        assert!(matches!(engine.go(&board, None, |_| ()), Err(EngineError::IllegalMove(m)) if m == "e2e4"));
+    }
@@ -0,0 +1,74 @@
+#!/bin/sh
+# minimal CECP engine used by tests of the CECP driver
+#
+# on go it sends thinking line with received moves and limits instead of principal variation
+# and plays e2e4 or e7e5, Nf3 or Nc6 in SAN mode
+#
+# with argument "v1" the engine sends no features, with "san" it asks for SAN moves,
+# with "slow" it moves only after "?"
+
+mode="$1"
+moves=""
+limits=""
+count=0
+
+while read -r line; do
+    set -- $line
+
+    case "$1" in
+        protover)
+            case "$mode" in
+                v1) ;;
+                san) echo 'feature myname="Fake CECP" san=1 setboard=1 ping=1 done=1' ;;
+                *) echo 'feature myname="Fake CECP" usermove=1 setboard=1'; echo 'feature ping=1 done=1' ;;
+            esac
+            ;;
+        new)
+            moves=""
+            limits=""
+            count=0
+            ;;
+        setboard)
+            shift
+            moves="[$*]"
+            ;;
+        usermove)
+            moves="$moves $2"
+            count=$((count + 1))
+            ;;
+        sd|st|level|time|otim)
+            limits="$limits $line"
+            ;;
+        ping)
+            echo "pong $2"
+            ;;
+        go)
+            echo "1 15 3 120 moves:$moves"
+            echo "2 25 10 200 limits:$limits"
+
+            if [ "$mode" = "slow" ]; then
+                continue
+            fi
+
+            if [ "$mode" = "san" ]; then
+                [ $((count % 2)) = 0 ] && echo "move Nf3" || echo "move Nc6"
+            else
+                [ $((count % 2)) = 0 ] && echo "move e2e4" || echo "move e7e5"
+            fi
+            ;;
+        "?")
+            echo "move d2d4"
+            ;;
+        quit)
+            exit 0
+            ;;
+        xboard|accepted|force|post|result)
+            ;;
+        *)
+            # moves without usermove in protocol version 1 and SAN mode
+            case "$1" in
+                [a-hKQRBNO]*) moves="$moves $1"; count=$((count + 1)) ;;
+            esac
+            ;;
+    esac
+done
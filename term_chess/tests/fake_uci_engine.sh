@@ -0,0 +1,61 @@
+#!/bin/sh
+# minimal UCI engine used by tests of the UCI driver
+#
+# "go depth n" sends n info lines and best move e2e4,
+# "go infinite", "go movetime" and "go nodes" send best move only after stop,
+# first line of info sent after go is the last received position
+#
+# with argument "silent" the engine never answers, with "stubborn" it ignores quit
+
+mode="$1"
+position="none"
+searching=0
+
+while read -r line; do
+    [ "$mode" = "silent" ] && continue
+
+    set -- $line
+
+    case "$1" in
+        uci)
+            echo "id name Fake Engine 1.0"
+            echo "id author term_chess tests"
+            echo "option name Hash type spin default 1 min 1 max 8"
+            echo "uciok"
+            ;;
+        isready)
+            echo "readyok"
+            ;;
+        position)
+            position="$line"
+            ;;
+        go)
+            echo "info string $position"
+
+            case "$line" in
+                *infinite*|*movetime*|*nodes*)
+                    searching=1
+                    ;;
+                *)
+                    depth="${3:-1}"
+                    i=1
+                    while [ "$i" -le "$depth" ]; do
+                        echo "info depth $i seldepth $i multipv 1 score cp $((i * 10)) nodes $((i * 100)) nps 1000 time $i pv e2e4 e7e5"
+                        i=$((i + 1))
+                    done
+                    echo "bestmove e2e4 ponder e7e5"
+                    ;;
+            esac
+            ;;
+        stop)
+            if [ "$searching" = 1 ]; then
+                echo "info depth 1 score mate 3 nodes 10 time 1 pv d2d4"
+                echo "bestmove d2d4"
+                searching=0
+            fi
+            ;;
+        quit)
+            [ "$mode" = "stubborn" ] || exit 0
+            ;;
+    esac
+done
@@ -0,0 +1,124 @@
+use std::time::{Duration, Instant};
+
+use chess_api::board::Board;
+use chess_api::engine::SearchLimits;
+use chess_api::game::Game;
+use chess_api::uci::{GoOptions, Score};
+use term_chess::engine::uci::UciEngine;
+use term_chess::engine::EngineError;
+
+const FAKE_ENGINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fake_uci_engine.sh");
+
+fn start(mode: &str) -> Result<UciEngine, EngineError> {
+    UciEngine::start("sh", &[FAKE_ENGINE, mode])
+}
+
+    #[test]
+    fn handshake() {
+        let mut engine = start("").unwrap();
+
+        assert_eq!(engine.name(), Some("Fake Engine 1.0"));
+        assert_eq!(engine.author(), Some("term_chess tests"));
+        assert_eq!(engine.options(), ["name Hash type spin default 1 min 1 max 8"]);
+
+        assert!(engine.new_game().is_ok());
+        assert!(engine.quit().is_ok());
+    }
+
+    #[test]
+    fn search_sends_position_and_parses_info() {
+        let mut engine = start("").unwrap();
+        let mut game = Game::from_board(Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap());
+        game.play_san("Kd2").unwrap();
+        game.play_san("Kd7").unwrap();
+
+        let mut lines = Vec::new();
+        let best = engine.search(&game, SearchLimits::depth(3), |info| lines.push(info.clone())).unwrap();
+
+        assert_eq!(lines[0].string.as_deref(), Some("position fen 4k3/8/8/8/8/8/4P3/4K3 w - - 0 1 moves e1d2 e8d7"));
+        assert_eq!(lines.len(), 4);
+
+        assert_eq!(best.m, Some("e2e4".parse().unwrap()));
+        assert_eq!(best.ponder, Some("e7e5".parse().unwrap()));
+
+        let info = best.info.unwrap();
+        assert_eq!(info.depth, Some(3));
+        assert_eq!(info.score, Some(Score::Centipawns(30)));
+        assert_eq!(info.nodes, Some(300));
+        assert_eq!(info.time, Some(Duration::from_millis(3)));
+        assert_eq!(info.pv.len(), 2);
+    }
+
+    #[test]
+    fn illegal_best_move_is_rejected() {
+        let mut engine = start("").unwrap();
+        let mut game = Game::new();
+        game.play_san("e4").unwrap();
+
+        // fake engine answers e2e4 in every position
+        let best = engine.search(&game, SearchLimits::depth(1), |_| ());
+        assert!(matches!(best, Err(EngineError::IllegalMove(m)) if m == "e2e4"));
+    }
+
+    #[test]
+    fn infinite_search_is_stopped() {
+        let mut engine = start("").unwrap();
+
+        engine.set_position(&Board::new(), &[]).unwrap();
+        engine.go(&GoOptions { infinite: true, ..GoOptions::default() }).unwrap();
+        engine.stop().unwrap();
+
+        let best = engine.wait_best_move(None, |_| ()).unwrap();
+
+        assert_eq!(best.m, Some("d2d4".parse().unwrap()));
+        assert_eq!(best.info.unwrap().score, Some(Score::Mate(3)));
+    }
+
+    #[test]
+    fn engine_exceeding_time_is_stopped() {
+        let mut engine = start("").unwrap();
+        engine.set_timeout(Duration::from_millis(200));
+
+        // fake engine never ends search with time limit by itself
+        let started = Instant::now();
+        let best = engine.search(&Game::new(), SearchLimits::time(Duration::from_millis(100)), |_| ()).unwrap();
+
+        assert_eq!(best.m, Some("d2d4".parse().unwrap()));
+        assert!(started.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn search_without_time_limit_is_stopped() {
+        let mut engine = start("").unwrap();
+        engine.set_timeout(Duration::from_millis(200));
+        engine.set_max_search_time(Duration::from_millis(100));
+
+        // fake engine never ends node limited search by itself
+        let started = Instant::now();
+        let best = engine.search(&Game::new(), SearchLimits { nodes: Some(1000), ..SearchLimits::default() }, |_| ()).unwrap();
+
+        assert_eq!(best.m, Some("d2d4".parse().unwrap()));
+        assert!(started.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn silent_engine_times_out() {
+        let started = Instant::now();
+        let engine = UciEngine::start_with_timeout("sh", &[FAKE_ENGINE, "silent"], Duration::from_millis(200));
+
+        assert!(matches!(engine, Err(EngineError::Timeout(expected)) if expected == "uciok"));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn stubborn_engine_is_killed() {
+        let mut engine = start("stubborn").unwrap();
+        engine.set_timeout(Duration::from_millis(100));
+
+        assert!(matches!(engine.quit(), Err(EngineError::Timeout(_))));
+    }
+
+    #[test]
+    fn missing_engine_is_reported() {
+        assert!(matches!(UciEngine::start("./no-such-engine", &[]), Err(EngineError::Spawn(_))));
+    }